@@ -0,0 +1,38 @@
+use std::{fs, process::Command};
+
+// Plumbs the version info shown on the credits screen in at compile time.
+fn main() {
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs");
+    println!("cargo:rerun-if-changed=Cargo.lock");
+
+    let build_hash = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|hash| hash.trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=BUILD_HASH={build_hash}");
+
+    // the resolved version, not the requirement in Cargo.toml
+    let bevy_version = fs::read_to_string("Cargo.lock")
+        .ok()
+        .and_then(|lock| {
+            let package = lock
+                .split("[[package]]")
+                .find(|package| package.lines().any(|line| line.trim() == "name = \"bevy\""))?;
+            let line = package
+                .lines()
+                .find(|line| line.trim().starts_with("version = "))?;
+            Some(
+                line.trim()
+                    .trim_start_matches("version = ")
+                    .trim_matches('"')
+                    .to_string(),
+            )
+        })
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=BEVY_VERSION={bevy_version}");
+}
@@ -0,0 +1,72 @@
+use accesskit::{Live, Node as AccessNode, Role};
+use bevy::{a11y::AccessibilityNode, prelude::*};
+
+use crate::{locale::Locale, GameOverEvent, GrowthEvent, SnakeSegments, Toast};
+
+// Hidden live region that gameplay announcements are written to.
+#[derive(Component)]
+struct Announcer;
+
+pub struct AccessibilityPlugin;
+
+impl Plugin for AccessibilityPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PostUpdate, label_text);
+
+        // off by default, they'd be a lot of chatter for most players
+        if std::env::args().any(|arg| arg == "--announce") {
+            app.add_systems(Startup, spawn_announcer)
+                .add_systems(PostUpdate, announce_gameplay);
+        }
+    }
+}
+
+// Exposes every piece of UI text to screen readers, kept in sync as it changes.
+// Toasts are live regions so they're read out as soon as they appear.
+fn label_text(
+    mut commands: Commands,
+    mut texts: Query<(Entity, &Text, Option<&mut AccessibilityNode>), Changed<Text>>,
+    toasts: Query<(), With<Toast>>,
+) {
+    for (ent, text, accessible) in texts.iter_mut() {
+        match accessible {
+            Some(mut accessible) => accessible.set_value(text.0.as_str()),
+            None => {
+                let mut node = AccessNode::new(Role::Label);
+                node.set_value(text.0.as_str());
+                if toasts.contains(ent) {
+                    node.set_live(Live::Polite);
+                }
+                commands.entity(ent).insert(AccessibilityNode(node));
+            }
+        }
+    }
+}
+
+fn spawn_announcer(mut commands: Commands) {
+    let mut node = AccessNode::new(Role::Status);
+    node.set_live(Live::Polite);
+    commands.spawn((AccessibilityNode(node), Announcer));
+}
+
+fn announce_gameplay(
+    locale: Res<Locale>,
+    mut growth_reader: EventReader<GrowthEvent>,
+    mut game_over_reader: EventReader<GameOverEvent>,
+    heads: Query<&SnakeSegments>,
+    mut announcer: Single<&mut AccessibilityNode, With<Announcer>>,
+) {
+    let mut announcement = None;
+    for GrowthEvent(head) in growth_reader.read() {
+        if let Ok(segments) = heads.get(*head) {
+            announcement = Some(locale.format("a11y-food-eaten", &[("length", &segments.0.len())]));
+        }
+    }
+    if game_over_reader.read().next().is_some() {
+        announcement = Some(locale.text("a11y-run-over").to_string());
+    }
+
+    if let Some(announcement) = announcement {
+        announcer.set_value(announcement);
+    }
+}
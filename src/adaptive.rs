@@ -0,0 +1,138 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::time::Duration;
+
+use crate::{
+    hot_reload::data_file, FixedTimer, FoodSpawnerTimer, GameMode, Player, SnakeDiedEvent,
+    SnakeHead, SnakeSegments,
+};
+
+// Tuning for adaptive difficulty, read from assets/adaptive.ron.
+#[derive(Resource, Deserialize)]
+pub struct AdaptiveConfig {
+    modes: Vec<GameMode>,
+    window_secs: f32,
+    adjust_secs: f32,
+    deaths_per_minute: (f32, f32),
+    length: (f32, f32),
+    step: f32,
+    min_difficulty: f32,
+    max_difficulty: f32,
+    base_tick_millis: u64,
+    base_food_secs: f32,
+}
+
+impl AdaptiveConfig {
+    fn load() -> Self {
+        Self::parse(&data_file!("adaptive.ron"))
+            .unwrap_or_else(|err| panic!("Broken adaptive difficulty config: {err}"))
+    }
+
+    pub fn parse(contents: &str) -> ron::error::SpannedResult<Self> {
+        ron::from_str(contents)
+    }
+}
+
+// Recent performance, as seconds of game time when each death happened
+// and player one's length sampled once a second.
+#[derive(Resource)]
+struct Performance {
+    deaths: Vec<f32>,
+    lengths: Vec<(f32, usize)>,
+    difficulty: f32,
+    sample_timer: Timer,
+    adjust_timer: Timer,
+}
+
+pub struct AdaptivePlugin;
+
+impl Plugin for AdaptivePlugin {
+    fn build(&self, app: &mut App) {
+        // always loaded so hot reloading has something to replace
+        let config = AdaptiveConfig::load();
+        let adjust_secs = config.adjust_secs;
+        app.insert_resource(config);
+        if !std::env::args().any(|arg| arg == "--adaptive") {
+            return;
+        }
+
+        app.insert_resource(Performance {
+            deaths: Vec::new(),
+            lengths: Vec::new(),
+            difficulty: 1.0,
+            sample_timer: Timer::from_seconds(1.0, TimerMode::Repeating),
+            adjust_timer: Timer::from_seconds(adjust_secs, TimerMode::Repeating),
+        })
+        .add_systems(Update, (track_performance, adapt_difficulty).chain());
+    }
+}
+
+fn track_performance(
+    time: Res<Time>,
+    config: Res<AdaptiveConfig>,
+    mut performance: ResMut<Performance>,
+    mut died_reader: EventReader<SnakeDiedEvent>,
+    heads: Query<(&SnakeHead, &SnakeSegments)>,
+) {
+    let now = time.elapsed_secs();
+    for _ in died_reader.read() {
+        performance.deaths.push(now);
+    }
+    if performance.sample_timer.tick(time.delta()).just_finished() {
+        if let Some((_, segments)) = heads.iter().find(|(head, _)| head.player == Player::One) {
+            performance.lengths.push((now, segments.0.len()));
+        }
+    }
+
+    let since = now - config.window_secs;
+    performance.deaths.retain(|at| *at >= since);
+    performance.lengths.retain(|(at, _)| *at >= since);
+}
+
+fn adapt_difficulty(
+    time: Res<Time>,
+    mode: Res<GameMode>,
+    config: Res<AdaptiveConfig>,
+    mut performance: ResMut<Performance>,
+    mut movement_timer: ResMut<FixedTimer>,
+    mut food_timer: ResMut<FoodSpawnerTimer>,
+) {
+    if !config.modes.contains(&mode) || !performance.adjust_timer.tick(time.delta()).just_finished()
+    {
+        return;
+    }
+
+    let window_minutes = time.elapsed_secs().min(config.window_secs) / 60.0;
+    let deaths_per_minute = performance.deaths.len() as f32 / window_minutes.max(f32::EPSILON);
+    let average_length = performance
+        .lengths
+        .iter()
+        .map(|(_, length)| *length as f32)
+        .sum::<f32>()
+        / performance.lengths.len().max(1) as f32;
+
+    let (fewest_deaths, most_deaths) = config.deaths_per_minute;
+    let (shortest, longest) = config.length;
+    let step = if deaths_per_minute > most_deaths || average_length < shortest {
+        -config.step
+    } else if deaths_per_minute < fewest_deaths && average_length > longest {
+        config.step
+    } else {
+        return;
+    };
+    let difficulty =
+        (performance.difficulty + step).clamp(config.min_difficulty, config.max_difficulty);
+    if difficulty == performance.difficulty {
+        return;
+    }
+    performance.difficulty = difficulty;
+
+    // harder means moving faster with food turning up less often
+    debug!("Adaptive difficulty now {difficulty:.1}");
+    movement_timer.0.set_duration(Duration::from_secs_f32(
+        config.base_tick_millis as f32 / 1000.0 / difficulty,
+    ));
+    food_timer
+        .0
+        .set_duration(Duration::from_secs_f32(config.base_food_secs * difficulty));
+}
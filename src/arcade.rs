@@ -0,0 +1,256 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::{
+    locale::Locale,
+    mutations::{Mutation, DRAFT_SIZE},
+    only_menu, show_toast, snake_growth, spawn_snake, ArenaItems, FixedTimer, FoodSpawnerTimer,
+    GameMode, GameOverEvent, GameRng, GrowthEvent, Lives, Menu, Score, SnakeSegment,
+};
+
+// Food to eat before a stage is cleared.
+const STAGE_FOOD: u32 = 10;
+// Every this many points is worth an extra life.
+const BONUS_LIFE_SCORE: u32 = 25;
+const FIRST_STAGE_TICK_MILLIS: u64 = 250;
+const STAGE_SPEEDUP_MILLIS: u64 = 25;
+const FASTEST_TICK_MILLIS: u64 = 100;
+const FOOD_SPAWN_SECS: f32 = 1.0;
+const INTERLUDE_SECS: f32 = 2.0;
+// A mutation is drafted on reaching every this many stages.
+const DRAFT_EVERY: u32 = 2;
+const DRAFT_KEYS: [KeyCode; DRAFT_SIZE] = [KeyCode::Digit1, KeyCode::Digit2, KeyCode::Digit3];
+
+// Where an arcade run is up to, kept with saved runs.
+#[derive(Resource, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Stage {
+    pub number: u32,
+    pub eaten: u32,
+    bonus_lives: u32,
+    pub mutations: Vec<Mutation>,
+    // segments still to add from mutations that grow the snake more
+    pub owed_growth: u32,
+}
+
+impl Default for Stage {
+    fn default() -> Self {
+        Self {
+            number: 1,
+            eaten: 0,
+            bonus_lives: 0,
+            mutations: Vec::new(),
+            owed_growth: 0,
+        }
+    }
+}
+
+impl Stage {
+    pub fn food_goal(&self) -> u32 {
+        STAGE_FOOD
+            + self
+                .mutations
+                .iter()
+                .map(|m| m.extra_stage_food())
+                .sum::<u32>()
+    }
+
+    fn tick_duration(&self) -> Duration {
+        let speedup = STAGE_SPEEDUP_MILLIS * (self.number as u64 - 1);
+        let millis = FIRST_STAGE_TICK_MILLIS
+            .saturating_sub(speedup)
+            .max(FASTEST_TICK_MILLIS);
+        let factor: f32 = self.mutations.iter().map(|m| m.tick_factor()).product();
+        Duration::from_millis(millis).mul_f32(factor)
+    }
+
+    fn food_spawn_duration(&self) -> Duration {
+        let factor: f32 = self
+            .mutations
+            .iter()
+            .map(|m| m.food_spawn_factor())
+            .product();
+        Duration::from_secs_f32(FOOD_SPAWN_SECS * factor)
+    }
+}
+
+// The "Stage N" banner shown between stages, timed in real time since the
+// game itself is paused underneath it. With a draft on offer it waits for
+// one to be picked first.
+#[derive(Component)]
+struct Interlude {
+    timer: Timer,
+    draft: Vec<Mutation>,
+}
+
+pub struct ArcadePlugin;
+
+impl Plugin for ArcadePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Stage>().add_systems(
+            Update,
+            (
+                (clear_stage.after(snake_growth), award_bonus_lives),
+                choose_mutation,
+                end_interlude,
+                reset_stage,
+                apply_stage_speed,
+            )
+                .chain(),
+        );
+    }
+}
+
+fn clear_stage(
+    mut commands: Commands,
+    locale: Res<Locale>,
+    mode: Res<GameMode>,
+    mut stage: ResMut<Stage>,
+    mut time: ResMut<Time<Virtual>>,
+    mut rng: ResMut<GameRng>,
+    mut growth_reader: EventReader<GrowthEvent>,
+) {
+    let eaten = growth_reader.read().count() as u32;
+    if *mode != GameMode::Arcade || eaten == 0 {
+        return;
+    }
+
+    stage.eaten += eaten;
+    if stage.eaten < stage.food_goal() {
+        return;
+    }
+    stage.number += 1;
+    stage.eaten = 0;
+
+    let draft = if (stage.number - 1).is_multiple_of(DRAFT_EVERY) {
+        Mutation::draft(&stage.mutations, &mut rng)
+    } else {
+        Vec::new()
+    };
+    let mut lines = vec![locale.format("arcade-stage", &[("stage", &stage.number)])];
+    if !draft.is_empty() {
+        lines.push(locale.text("arcade-draft").to_string());
+    }
+    for (i, mutation) in draft.iter().enumerate() {
+        lines.push(format!(
+            "{}. {}: {}",
+            i + 1,
+            mutation.name(&locale),
+            mutation.description(&locale)
+        ));
+    }
+
+    time.pause();
+    commands.spawn((
+        Text::new(lines.join("\n")),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Percent(40.0),
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        TextLayout::new_with_justify(JustifyText::Center),
+        TextFont::from_font_size(32.0),
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+        Interlude {
+            timer: Timer::from_seconds(INTERLUDE_SECS, TimerMode::Once),
+            draft,
+        },
+        Menu,
+    ));
+}
+
+fn award_bonus_lives(
+    mut commands: Commands,
+    locale: Res<Locale>,
+    mode: Res<GameMode>,
+    score: Res<Score>,
+    mut stage: ResMut<Stage>,
+    mut lives: ResMut<Lives>,
+) {
+    if *mode != GameMode::Arcade
+        || !score.is_changed()
+        || stage.mutations.contains(&Mutation::Bulwark)
+    {
+        return;
+    }
+
+    let earned = score.0 / BONUS_LIFE_SCORE;
+    if earned > stage.bonus_lives {
+        lives.0 += earned - stage.bonus_lives;
+        stage.bonus_lives = earned;
+        show_toast(&mut commands, locale.text("toast-bonus-life"));
+    }
+}
+
+fn choose_mutation(
+    locale: Res<Locale>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut stage: ResMut<Stage>,
+    mut interludes: Query<(Entity, &mut Interlude, &mut Text)>,
+    menus: Query<Entity, With<Menu>>,
+) {
+    let Ok((ent, mut interlude, mut text)) = interludes.get_single_mut() else {
+        return;
+    };
+    if !only_menu(ent, &menus) {
+        return;
+    }
+
+    let chosen = DRAFT_KEYS
+        .iter()
+        .zip(&interlude.draft)
+        .find(|(key, _)| keyboard_input.just_pressed(**key))
+        .map(|(_, mutation)| *mutation);
+    if let Some(mutation) = chosen {
+        stage.mutations.push(mutation);
+        interlude.draft.clear();
+        text.0 = locale.format("arcade-mutated", &[("mutation", &mutation.name(&locale))]);
+    }
+}
+
+// Each stage starts on a fresh board with the snake back at its start.
+fn end_interlude(
+    mut commands: Commands,
+    real_time: Res<Time<Real>>,
+    mode: Res<GameMode>,
+    mut time: ResMut<Time<Virtual>>,
+    mut interludes: Query<(Entity, &mut Interlude)>,
+    board: Query<Entity, Or<(ArenaItems, With<SnakeSegment>)>>,
+) {
+    let Ok((ent, mut interlude)) = interludes.get_single_mut() else {
+        return;
+    };
+    if !interlude.draft.is_empty() || !interlude.timer.tick(real_time.delta()).finished() {
+        return;
+    }
+
+    commands.entity(ent).despawn();
+    for ent in board.iter() {
+        commands.entity(ent).despawn();
+    }
+    for player in mode.players() {
+        spawn_snake(&mut commands, *player);
+    }
+    time.unpause();
+}
+
+fn reset_stage(mut reader: EventReader<GameOverEvent>, mut stage: ResMut<Stage>) {
+    if reader.read().next().is_some() {
+        *stage = Stage::default();
+    }
+}
+
+fn apply_stage_speed(
+    mode: Res<GameMode>,
+    stage: Res<Stage>,
+    mut movement_timer: ResMut<FixedTimer>,
+    mut food_timer: ResMut<FoodSpawnerTimer>,
+) {
+    if *mode == GameMode::Arcade && stage.is_changed() {
+        movement_timer.0.set_duration(stage.tick_duration());
+        food_timer.0.set_duration(stage.food_spawn_duration());
+    }
+}
@@ -0,0 +1,72 @@
+use bevy::{
+    prelude::*,
+    window::{WindowFocused, WindowOccluded},
+};
+use std::time::Duration;
+
+use crate::FixedTimer;
+
+// Most game time a single frame can advance by. Kept under the fastest
+// tick so one long frame never adds up to more than one move.
+const MAX_FRAME_DELTA: Duration = Duration::from_millis(50);
+// A real frame this long means the process was suspended rather than
+// just slow, e.g. a laptop lid closing without the window losing focus.
+const STALL: Duration = Duration::from_secs(1);
+
+pub struct BackgroundPausePlugin;
+
+impl Plugin for BackgroundPausePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, clamp_frame_delta)
+            .add_systems(PreUpdate, (pause_in_background, absorb_stalls));
+    }
+}
+
+fn clamp_frame_delta(mut time: ResMut<Time<Virtual>>) {
+    time.set_max_delta(MAX_FRAME_DELTA);
+}
+
+// Pauses while no window is focused or every one is hidden, and only
+// resumes a pause it started itself so menus keep theirs. Focus moving
+// between the game's own windows doesn't count.
+fn pause_in_background(
+    mut paused_here: Local<bool>,
+    mut occluded: Local<Vec<Entity>>,
+    mut focus_reader: EventReader<WindowFocused>,
+    mut occluded_reader: EventReader<WindowOccluded>,
+    windows: Query<(Entity, &Window)>,
+    mut time: ResMut<Time<Virtual>>,
+    mut timer: ResMut<FixedTimer>,
+) {
+    let focus_changed = focus_reader.read().count() > 0;
+    let mut visibility_changed = false;
+    for event in occluded_reader.read() {
+        occluded.retain(|ent| *ent != event.window);
+        if event.occluded {
+            occluded.push(event.window);
+        }
+        visibility_changed = true;
+    }
+    if !focus_changed && !visibility_changed {
+        return;
+    }
+    let foreground = windows.iter().any(|(_, window)| window.focused)
+        && windows.iter().any(|(ent, _)| !occluded.contains(&ent));
+
+    if !foreground && !time.is_paused() {
+        time.pause();
+        *paused_here = true;
+    } else if foreground && *paused_here {
+        time.unpause();
+        *paused_here = false;
+        // a full tick before the first move, so the player can get their
+        // bearings back
+        timer.0.reset();
+    }
+}
+
+fn absorb_stalls(real_time: Res<Time<Real>>, mut timer: ResMut<FixedTimer>) {
+    if real_time.delta() >= STALL {
+        timer.0.reset();
+    }
+}
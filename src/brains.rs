@@ -0,0 +1,276 @@
+use bevy::prelude::*;
+use rand::seq::SliceRandom;
+use std::time::{Duration, Instant};
+
+use crate::{
+    arg_value, obstacles::Blocking, planner, snake_movement, Direction, FixedTimer, Food, GameRng,
+    Player, Position, SnakeHead, Trail, ARENA_HEIGHT, ARENA_WIDTH,
+};
+
+// Time an A* brain gets to plan a move before it falls back to greedy.
+const ROUTE_BUDGET: Duration = Duration::from_millis(2);
+const DIRECTIONS: [Direction; 4] = [
+    Direction::Left,
+    Direction::Up,
+    Direction::Right,
+    Direction::Down,
+];
+
+// What a brain gets to see when picking its snake's next move.
+pub struct BoardView<'a> {
+    pub head: Position,
+    pub direction: Direction,
+    // every snake segment, block and light-cycle trail, its own body included
+    pub blocked: &'a [Position],
+    pub food: &'a [Position],
+    // for any brain that plays a chance, so seeded runs still play out the same
+    pub rng: &'a mut GameRng,
+}
+
+impl BoardView<'_> {
+    pub fn is_safe(&self, direction: Direction) -> bool {
+        let next = self.head.moved(direction);
+        direction != self.direction.opposite() && next.in_arena() && !self.blocked.contains(&next)
+    }
+
+    pub fn safe_moves(&self) -> Vec<Direction> {
+        DIRECTIONS
+            .into_iter()
+            .filter(|dir| self.is_safe(*dir))
+            .collect()
+    }
+}
+
+// Steers a computer-controlled snake, asked once after every move for the
+// direction of the next.
+pub trait SnakeBrain: Send + Sync {
+    fn decide(&mut self, view: BoardView) -> Direction;
+}
+
+type BrainMaker = fn() -> Box<dyn SnakeBrain>;
+
+// Brains by name, and which players are handed to one. Anything wanting a
+// new kind of bot registers it here rather than adding its own steering.
+#[derive(Resource)]
+pub struct Brains {
+    makers: Vec<(&'static str, BrainMaker)>,
+    assigned: Vec<(Player, String)>,
+}
+
+impl Brains {
+    pub fn register(&mut self, name: &'static str, maker: BrainMaker) {
+        self.makers.retain(|(known, _)| *known != name);
+        self.makers.push((name, maker));
+    }
+
+    // Hands `player` to the brain called `name`, or back to its controls
+    // with "none".
+    pub fn assign(&mut self, player: Player, name: &str) {
+        if name != "none" && !self.makers.iter().any(|(known, _)| *known == name) {
+            warn!("No snake brain called {name}");
+            return;
+        }
+        self.assigned.retain(|(assigned, _)| *assigned != player);
+        if name != "none" {
+            self.assigned.push((player, name.to_string()));
+        }
+    }
+
+    fn make(&self, player: Player) -> Option<Box<dyn SnakeBrain>> {
+        let (_, name) = self
+            .assigned
+            .iter()
+            .find(|(assigned, _)| *assigned == player)?;
+        let (_, maker) = self.makers.iter().find(|(known, _)| known == name)?;
+        Some(maker())
+    }
+}
+
+// A snake steered by a brain instead of a player's controls.
+#[derive(Component)]
+pub struct Bot(Box<dyn SnakeBrain>);
+
+// Heads for the nearest food by straight-line distance.
+struct Greedy;
+
+impl SnakeBrain for Greedy {
+    fn decide(&mut self, view: BoardView) -> Direction {
+        let distance = |dir: Direction| {
+            let next = view.head.moved(dir);
+            view.food
+                .iter()
+                .map(|food| (food.x - next.x).abs() + (food.y - next.y).abs())
+                .min()
+                .unwrap_or(0)
+        };
+        let safe = view.safe_moves();
+        if view.food.is_empty() && safe.contains(&view.direction) {
+            return view.direction;
+        }
+        safe.into_iter()
+            .min_by_key(|dir| distance(*dir))
+            .unwrap_or(view.direction)
+    }
+}
+
+// Plans a path around everything in the way to the nearest food.
+struct AStar;
+
+impl SnakeBrain for AStar {
+    fn decide(&mut self, view: BoardView) -> Direction {
+        let deadline = Instant::now() + ROUTE_BUDGET;
+        planner::route(view.head, view.food, view.blocked, deadline)
+            .and_then(|path| path.first().copied())
+            .and_then(|next| {
+                DIRECTIONS
+                    .into_iter()
+                    .find(|dir| view.head.moved(*dir) == next)
+            })
+            .filter(|dir| view.is_safe(*dir))
+            .unwrap_or_else(|| Greedy.decide(view))
+    }
+}
+
+// Follows one fixed loop through every cell, which can't run into itself
+// however long the snake grows. Column 0 is the way back down; the rest is
+// swept row by row, which needs an even number of rows to close the loop.
+struct Hamiltonian;
+
+impl Hamiltonian {
+    fn along_cycle(pos: Position) -> Direction {
+        let last_column = ARENA_WIDTH as i32 - 1;
+        let last_row = ARENA_HEIGHT as i32 - 1;
+        if pos.x == 0 {
+            if pos.y > 0 {
+                Direction::Down
+            } else {
+                Direction::Right
+            }
+        } else if pos.y % 2 == 0 {
+            if pos.x < last_column {
+                Direction::Right
+            } else {
+                Direction::Up
+            }
+        } else if pos.x > 1 || pos.y == last_row {
+            Direction::Left
+        } else {
+            Direction::Up
+        }
+    }
+}
+
+impl SnakeBrain for Hamiltonian {
+    fn decide(&mut self, view: BoardView) -> Direction {
+        let dir = Self::along_cycle(view.head);
+        if view.is_safe(dir) {
+            dir
+        } else {
+            // off the loop after a respawn or a teleport, until it finds it again
+            Greedy.decide(view)
+        }
+    }
+}
+
+// Any move that doesn't crash straight away.
+struct Wander;
+
+impl SnakeBrain for Wander {
+    fn decide(&mut self, view: BoardView) -> Direction {
+        view.safe_moves()
+            .choose(&mut view.rng.0)
+            .copied()
+            .unwrap_or(view.direction)
+    }
+}
+
+// Bots picked on the command line as `--bot two:astar`, or several
+// comma separated.
+fn bots_from_args(brains: &mut Brains) {
+    let Some(arg) = arg_value("--bot") else {
+        return;
+    };
+    for pick in arg.split(',') {
+        let (player, name) = pick.split_once(':').unwrap_or(("two", pick));
+        let player = match player {
+            "one" | "1" => Player::One,
+            _ => Player::Two,
+        };
+        brains.assign(player, name);
+    }
+}
+
+pub struct BrainsPlugin;
+
+impl Plugin for BrainsPlugin {
+    fn build(&self, app: &mut App) {
+        let mut brains = Brains {
+            makers: Vec::new(),
+            assigned: Vec::new(),
+        };
+        brains.register("greedy", || Box::new(Greedy));
+        brains.register("astar", || Box::new(AStar));
+        brains.register("hamiltonian", || Box::new(Hamiltonian));
+        brains.register("random", || Box::new(Wander));
+        bots_from_args(&mut brains);
+
+        app.insert_resource(brains).add_systems(
+            Update,
+            (assign_bots, drive_bots).chain().after(snake_movement),
+        );
+    }
+}
+
+// Keeps each snake's brain in step with the assignments, respawns included.
+fn assign_bots(
+    mut commands: Commands,
+    brains: Res<Brains>,
+    heads: Query<(Entity, &SnakeHead, Has<Bot>)>,
+    added: Query<(), Added<SnakeHead>>,
+) {
+    for (ent, head, has_bot) in heads.iter() {
+        if !brains.is_changed() && !added.contains(ent) {
+            continue;
+        }
+        match brains.make(head.player) {
+            Some(brain) => {
+                commands.entity(ent).insert(Bot(brain));
+            }
+            None if has_bot => {
+                commands.entity(ent).remove::<Bot>();
+            }
+            None => {}
+        }
+    }
+}
+
+// Asks each brain for its next move once the snakes have moved, and as soon
+// as a snake gets one.
+fn drive_bots(
+    timer: Res<FixedTimer>,
+    mut bots: Query<(&mut SnakeHead, &Position, &mut Bot)>,
+    added: Query<(), Added<Bot>>,
+    blocking: Query<&Position, Blocking>,
+    trails: Query<&Position, With<Trail>>,
+    food: Query<&Position, With<Food>>,
+    mut rng: ResMut<GameRng>,
+) {
+    if !timer.0.just_finished() && added.is_empty() {
+        return;
+    }
+
+    let blocked: Vec<Position> = blocking.iter().chain(trails.iter()).copied().collect();
+    let food: Vec<Position> = food.iter().copied().collect();
+    for (mut head, head_pos, mut bot) in bots.iter_mut() {
+        let direction = bot.0.decide(BoardView {
+            head: *head_pos,
+            direction: head.direction,
+            blocked: &blocked,
+            food: &food,
+            rng: &mut rng,
+        });
+        if direction != head.direction.opposite() {
+            head.direction = direction;
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use bevy::prelude::*;
+use bevy::tasks::{block_on, poll_once, IoTaskPool, Task};
+use std::time::Duration;
+
+use crate::{
+    arg_value,
+    locale::Locale,
+    profiles::{Profile, ProfileSelector},
+    progress::Progress,
+    show_toast,
+};
+
+const SYNC_TOKEN_VAR: &str = "SNAKE_SYNC_TOKEN";
+// A hung endpoint fails the sync after this long rather than blocking every
+// sync after it.
+const SYNC_TIMEOUT: Duration = Duration::from_secs(10);
+
+// Endpoint given with `--sync-url`, the bearer token comes from the environment
+// so it doesn't end up in shell history. The default profile syncs with the
+// URL itself and any other with `<url>/<profile>`, so family members sharing
+// an endpoint each keep their own copy.
+#[derive(Resource)]
+struct SyncConfig {
+    url: String,
+    token: Option<String>,
+    agent: ureq::Agent,
+}
+
+enum SyncOutcome {
+    Pulled(Box<Progress>),
+    Pushed,
+}
+
+impl SyncConfig {
+    fn url(&self, profile: &Profile) -> String {
+        if profile.is_default() {
+            self.url.clone()
+        } else {
+            format!("{}/{}", self.url.trim_end_matches('/'), profile.name())
+        }
+    }
+
+    // Starts syncing `profile`'s progress in the background.
+    fn start(&self, profile: &Profile, progress: &Progress) -> Option<PendingTask> {
+        let local = match progress.to_ron() {
+            Ok(local) => local,
+            Err(err) => {
+                warn!("Failed to serialize progress for sync: {err}");
+                return None;
+            }
+        };
+        let agent = self.agent.clone();
+        let url = self.url(profile);
+        let token = self.token.clone();
+        let local_saved_at = progress.saved_at;
+        let task = IoTaskPool::get()
+            .spawn(async move { sync(&agent, &url, token.as_deref(), local_saved_at, &local) });
+        Some((profile.clone(), task))
+    }
+}
+
+// The profile a sync was started for, whose progress it may replace.
+type PendingTask = (Profile, Task<Result<SyncOutcome, String>>);
+
+#[derive(Resource, Default)]
+struct PendingSync(Option<PendingTask>);
+
+pub struct CloudSyncPlugin;
+
+impl Plugin for CloudSyncPlugin {
+    fn build(&self, app: &mut App) {
+        let Some(url) = arg_value("--sync-url") else {
+            return;
+        };
+
+        app.insert_resource(SyncConfig {
+            url,
+            token: std::env::var(SYNC_TOKEN_VAR).ok(),
+            agent: ureq::AgentBuilder::new()
+                .timeout_connect(SYNC_TIMEOUT)
+                .timeout(SYNC_TIMEOUT)
+                .build(),
+        })
+        .insert_resource(PendingSync::default())
+        .add_systems(Update, (sync_on_launch, sync_on_key, finish_sync));
+    }
+}
+
+// Syncs once on launch, after the profile selector has been answered so
+// it's the chosen profile that's synced.
+fn sync_on_launch(
+    mut synced: Local<bool>,
+    config: Res<SyncConfig>,
+    profile: Res<Profile>,
+    progress: Res<Progress>,
+    selector: Query<(), With<ProfileSelector>>,
+    mut pending: ResMut<PendingSync>,
+) {
+    if *synced || !selector.is_empty() || pending.0.is_some() {
+        return;
+    }
+    *synced = true;
+    pending.0 = config.start(&profile, &progress);
+}
+
+fn sync_on_key(
+    mut commands: Commands,
+    locale: Res<Locale>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    config: Res<SyncConfig>,
+    profile: Res<Profile>,
+    progress: Res<Progress>,
+    mut pending: ResMut<PendingSync>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::F5) {
+        return;
+    }
+    if pending.0.is_some() {
+        show_toast(&mut commands, locale.text("sync-busy"));
+        return;
+    }
+    pending.0 = config.start(&profile, &progress);
+}
+
+// Pulls the remote copy if it is newer than ours, otherwise pushes ours.
+fn sync(
+    agent: &ureq::Agent,
+    url: &str,
+    token: Option<&str>,
+    local_saved_at: u64,
+    local: &str,
+) -> Result<SyncOutcome, String> {
+    let authorize = |request: ureq::Request| match token {
+        Some(token) => request.set("Authorization", &format!("Bearer {token}")),
+        None => request,
+    };
+
+    let remote = match authorize(agent.get(url)).call() {
+        Ok(response) => {
+            let body = response.into_string().map_err(|err| err.to_string())?;
+            Some(ron::from_str::<Progress>(&body).map_err(|err| err.to_string())?)
+        }
+        Err(ureq::Error::Status(404, _)) => None,
+        Err(err) => return Err(err.to_string()),
+    };
+
+    match remote {
+        Some(remote) if remote.saved_at > local_saved_at => {
+            Ok(SyncOutcome::Pulled(Box::new(remote)))
+        }
+        _ => {
+            authorize(agent.put(url))
+                .send_string(local)
+                .map_err(|err| err.to_string())?;
+            Ok(SyncOutcome::Pushed)
+        }
+    }
+}
+
+fn finish_sync(
+    mut commands: Commands,
+    locale: Res<Locale>,
+    profile: Res<Profile>,
+    mut pending: ResMut<PendingSync>,
+    mut progress: ResMut<Progress>,
+) {
+    let Some((synced_profile, task)) = pending.0.as_mut() else {
+        return;
+    };
+    let Some(outcome) = block_on(poll_once(task)) else {
+        return;
+    };
+    // a profile switched to since has its own progress, not this one's
+    let stale = *synced_profile != *profile;
+    pending.0 = None;
+    if stale {
+        return;
+    }
+
+    match outcome {
+        Ok(SyncOutcome::Pulled(remote)) => {
+            progress.adopt(*remote);
+            progress.write();
+            show_toast(&mut commands, locale.text("sync-pulled"));
+        }
+        Ok(SyncOutcome::Pushed) => {
+            show_toast(&mut commands, locale.text("sync-pushed"));
+        }
+        Err(err) => {
+            warn!("Cloud sync failed: {err}");
+            show_toast(&mut commands, locale.text("sync-failed"));
+        }
+    }
+}
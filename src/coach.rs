@@ -0,0 +1,79 @@
+use bevy::{prelude::*, window::PrimaryWindow};
+use std::time::{Duration, Instant};
+
+use crate::{
+    cell_center, obstacles::Block, planner, progress::Progress, Food, Player, Position, SnakeHead,
+    SnakeSegment, Trail,
+};
+
+// Longest the planner may take in a frame before the old route is kept.
+const PLAN_BUDGET: Duration = Duration::from_millis(2);
+const ROUTE_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.25);
+
+// Cells the route has to go around.
+type Obstacles = Or<(With<SnakeSegment>, With<Trail>, With<Block>)>;
+
+// Route the planner suggests for player one, from the head to the nearest food.
+#[derive(Resource, Default)]
+struct Coach(Vec<Position>);
+
+pub struct CoachPlugin;
+
+impl Plugin for CoachPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Coach>()
+            .add_systems(Update, (plan_route, draw_route).chain());
+    }
+}
+
+fn plan_route(
+    progress: Res<Progress>,
+    mut coach: ResMut<Coach>,
+    heads: Query<(Entity, &SnakeHead, &Position)>,
+    obstacles: Query<(Entity, &Position), Obstacles>,
+    food: Query<&Position, With<Food>>,
+) {
+    if !progress.coach {
+        return;
+    }
+    let Some((head_entity, _, start)) =
+        heads.iter().find(|(_, head, _)| head.player == Player::One)
+    else {
+        coach.0.clear();
+        return;
+    };
+
+    let goals: Vec<Position> = food.iter().copied().collect();
+    let blocked: Vec<Position> = obstacles
+        .iter()
+        .filter(|(ent, _)| *ent != head_entity)
+        .map(|(_, pos)| *pos)
+        .collect();
+    let deadline = Instant::now() + PLAN_BUDGET;
+    match planner::route(*start, &goals, &blocked, deadline) {
+        Some(route) => coach.0 = route,
+        // no way through to any food, nothing worth suggesting
+        None if Instant::now() <= deadline => coach.0.clear(),
+        None => {}
+    }
+}
+
+fn draw_route(
+    progress: Res<Progress>,
+    coach: Res<Coach>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    heads: Query<(&SnakeHead, &Position)>,
+    mut gizmos: Gizmos,
+) {
+    if !progress.coach || coach.0.is_empty() {
+        return;
+    }
+    let Some((_, start)) = heads.iter().find(|(head, _)| head.player == Player::One) else {
+        return;
+    };
+
+    let points = std::iter::once(start)
+        .chain(&coach.0)
+        .map(|pos| cell_center(*pos, progress.orientation, &window));
+    gizmos.linestrip_2d(points, ROUTE_COLOR);
+}
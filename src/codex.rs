@@ -0,0 +1,179 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    locale::Locale, pickups::Pickups, progress::Progress, show_toast, Food, Menu, Player, PowerUp,
+    Trail,
+};
+
+const ICON_SIZE: f32 = 14.0;
+const LOCKED_COLOR: Color = Color::srgb(0.3, 0.3, 0.3);
+
+// Something in the arena with an entry in the codex.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CodexEntry {
+    Food,
+    PowerUp(PowerUp),
+    Trail,
+}
+
+impl CodexEntry {
+    fn name(self, locale: &Locale) -> &str {
+        match self {
+            Self::Food => locale.text("codex-food"),
+            Self::PowerUp(power_up) => power_up.name(locale),
+            Self::Trail => locale.text("codex-trail"),
+        }
+    }
+
+    fn effect(self, locale: &Locale) -> &str {
+        locale.text(match self {
+            Self::Food => "codex-food-effect",
+            Self::PowerUp(PowerUp::Projectile) => "codex-projectile-effect",
+            Self::PowerUp(PowerUp::Freeze) => "codex-freeze-effect",
+            Self::PowerUp(PowerUp::Confusion) => "codex-confusion-effect",
+            Self::PowerUp(PowerUp::Shield) => "codex-shield-effect",
+            Self::PowerUp(PowerUp::Energy) => "codex-energy-effect",
+            Self::Trail => "codex-trail-effect",
+        })
+    }
+}
+
+// Every entry with its icon colour, food and power-ups straight from the
+// pickup definitions so new ones show up without touching this.
+fn entries(pickups: &Pickups, colorblind: bool) -> Vec<(CodexEntry, Color)> {
+    let mut entries = vec![(CodexEntry::Food, pickups.food.color_for(colorblind))];
+    entries.extend(pickups.power_ups().map(|def| {
+        (
+            CodexEntry::PowerUp(def.effect),
+            def.look.color_for(colorblind),
+        )
+    }));
+    let (_, trail_color) = Player::One.colors();
+    entries.push((CodexEntry::Trail, trail_color));
+    entries
+}
+
+#[derive(Component)]
+struct Codex;
+
+pub struct CodexPlugin;
+
+impl Plugin for CodexPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (discover_entries, toggle_codex, fill_codex).chain());
+    }
+}
+
+// Entries unlock the first time their thing turns up in the arena.
+fn discover_entries(
+    mut commands: Commands,
+    locale: Res<Locale>,
+    mut progress: ResMut<Progress>,
+    food: Query<(), Added<Food>>,
+    power_ups: Query<&PowerUp, Added<PowerUp>>,
+    trails: Query<(), Added<Trail>>,
+) {
+    let seen = (!food.is_empty())
+        .then_some(CodexEntry::Food)
+        .into_iter()
+        .chain(
+            power_ups
+                .iter()
+                .map(|power_up| CodexEntry::PowerUp(*power_up)),
+        )
+        .chain((!trails.is_empty()).then_some(CodexEntry::Trail));
+
+    let mut discovered_any = false;
+    for entry in seen {
+        if progress.codex.contains(&entry) {
+            continue;
+        }
+        progress.codex.push(entry);
+        show_toast(
+            &mut commands,
+            locale.format("toast-codex-entry", &[("entry", &entry.name(&locale))]),
+        );
+        discovered_any = true;
+    }
+    if discovered_any {
+        progress.save();
+    }
+}
+
+fn toggle_codex(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    codex: Query<Entity, With<Codex>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::KeyX) {
+        return;
+    }
+
+    match codex.get_single() {
+        Ok(ent) => commands.entity(ent).despawn_recursive(),
+        Err(_) => {
+            commands.spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(5.0),
+                    left: Val::Px(5.0),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(4.0),
+                    padding: UiRect::all(Val::Px(4.0)),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+                Codex,
+                Menu,
+            ));
+        }
+    }
+}
+
+// A row per entry, an icon beside the name and what it does, or just a
+// grey square and question marks until it's been found.
+fn fill_codex(
+    mut commands: Commands,
+    locale: Res<Locale>,
+    pickups: Res<Pickups>,
+    progress: Res<Progress>,
+    codex: Query<(Entity, Ref<Codex>)>,
+) {
+    let Ok((panel, added)) = codex.get_single() else {
+        return;
+    };
+    if !added.is_added() && !progress.is_changed() && !pickups.is_changed() {
+        return;
+    }
+
+    commands.entity(panel).despawn_descendants();
+    commands.entity(panel).with_children(|panel| {
+        panel.spawn(Text::new(locale.text("codex-title")));
+        for (entry, color) in entries(&pickups, progress.colorblind) {
+            let found = progress.codex.contains(&entry);
+            let line = if found {
+                format!("{}: {}", entry.name(&locale), entry.effect(&locale))
+            } else {
+                locale.text("codex-locked").to_string()
+            };
+            panel
+                .spawn(Node {
+                    column_gap: Val::Px(6.0),
+                    align_items: AlignItems::Center,
+                    ..default()
+                })
+                .with_children(|row| {
+                    row.spawn((
+                        Node {
+                            width: Val::Px(ICON_SIZE),
+                            height: Val::Px(ICON_SIZE),
+                            ..default()
+                        },
+                        BackgroundColor(if found { color } else { LOCKED_COLOR }),
+                    ));
+                    row.spawn(Text::new(line));
+                });
+        }
+    });
+}
@@ -0,0 +1,354 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{locale::Locale, only_menu, progress::Progress, show_toast, Direction, Menu, Player};
+
+// Keys the menus and panels already listen for, these can't be bound.
+const RESERVED_KEYS: [KeyCode; 38] = [
+    KeyCode::Escape,
+    KeyCode::Enter,
+    KeyCode::KeyQ,
+    KeyCode::Backspace,
+    KeyCode::KeyB,
+    KeyCode::KeyC,
+    KeyCode::KeyG,
+    KeyCode::KeyK,
+    KeyCode::KeyL,
+    KeyCode::KeyM,
+    KeyCode::KeyT,
+    KeyCode::KeyX,
+    KeyCode::Minus,
+    KeyCode::Equal,
+    KeyCode::BracketLeft,
+    KeyCode::BracketRight,
+    KeyCode::F1,
+    KeyCode::F2,
+    KeyCode::F3,
+    KeyCode::F4,
+    KeyCode::F5,
+    KeyCode::F6,
+    KeyCode::F7,
+    KeyCode::F8,
+    KeyCode::F9,
+    KeyCode::F10,
+    KeyCode::F11,
+    KeyCode::F12,
+    KeyCode::Digit0,
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+];
+
+// Keys only taken with the `debug-tools` feature, by the tick scrubber and
+// the simulation speed controls.
+const DEBUG_KEYS: [KeyCode; 7] = [
+    KeyCode::Comma,
+    KeyCode::Period,
+    KeyCode::PageUp,
+    KeyCode::PageDown,
+    KeyCode::Home,
+    KeyCode::End,
+    KeyCode::Pause,
+];
+
+fn is_reserved(key: KeyCode) -> bool {
+    RESERVED_KEYS.contains(&key) || (cfg!(feature = "debug-tools") && DEBUG_KEYS.contains(&key))
+}
+
+const ACTION_KEYS: [KeyCode; 10] = [
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+    KeyCode::Digit0,
+];
+
+#[derive(Clone, Copy, PartialEq)]
+enum Action {
+    Move(Direction),
+    Fire,
+}
+
+impl Action {
+    const ALL: [Self; 5] = [
+        Self::Move(Direction::Left),
+        Self::Move(Direction::Down),
+        Self::Move(Direction::Up),
+        Self::Move(Direction::Right),
+        Self::Fire,
+    ];
+
+    fn name(self, locale: &Locale) -> &str {
+        locale.text(match self {
+            Self::Move(Direction::Left) => "action-left",
+            Self::Move(Direction::Down) => "action-down",
+            Self::Move(Direction::Up) => "action-up",
+            Self::Move(Direction::Right) => "action-right",
+            Self::Fire => "action-fire",
+        })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlayerKeys {
+    left: KeyCode,
+    down: KeyCode,
+    up: KeyCode,
+    right: KeyCode,
+    fire: KeyCode,
+}
+
+impl PlayerKeys {
+    pub fn directions(&self) -> [(KeyCode, Direction); 4] {
+        [
+            (self.left, Direction::Left),
+            (self.down, Direction::Down),
+            (self.up, Direction::Up),
+            (self.right, Direction::Right),
+        ]
+    }
+
+    pub fn fire(&self) -> KeyCode {
+        self.fire
+    }
+
+    fn key_mut(&mut self, action: Action) -> &mut KeyCode {
+        match action {
+            Action::Move(Direction::Left) => &mut self.left,
+            Action::Move(Direction::Down) => &mut self.down,
+            Action::Move(Direction::Up) => &mut self.up,
+            Action::Move(Direction::Right) => &mut self.right,
+            Action::Fire => &mut self.fire,
+        }
+    }
+
+    fn key(&self, action: Action) -> KeyCode {
+        match action {
+            Action::Move(Direction::Left) => self.left,
+            Action::Move(Direction::Down) => self.down,
+            Action::Move(Direction::Up) => self.up,
+            Action::Move(Direction::Right) => self.right,
+            Action::Fire => self.fire,
+        }
+    }
+}
+
+// Keyboard bindings for both players, saved per profile.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Controls {
+    one: PlayerKeys,
+    two: PlayerKeys,
+}
+
+impl Default for Controls {
+    fn default() -> Self {
+        Self {
+            one: PlayerKeys {
+                left: KeyCode::ArrowLeft,
+                down: KeyCode::ArrowDown,
+                up: KeyCode::ArrowUp,
+                right: KeyCode::ArrowRight,
+                fire: KeyCode::ShiftRight,
+            },
+            two: PlayerKeys {
+                left: KeyCode::KeyA,
+                down: KeyCode::KeyS,
+                up: KeyCode::KeyW,
+                right: KeyCode::KeyD,
+                fire: KeyCode::ShiftLeft,
+            },
+        }
+    }
+}
+
+impl Controls {
+    pub fn player(&self, player: Player) -> &PlayerKeys {
+        match player {
+            Player::One => &self.one,
+            Player::Two => &self.two,
+        }
+    }
+
+    fn player_mut(&mut self, player: Player) -> &mut PlayerKeys {
+        match player {
+            Player::One => &mut self.one,
+            Player::Two => &mut self.two,
+        }
+    }
+
+    // Every binding in the order the remapping screen lists them.
+    fn bindings(&self) -> impl Iterator<Item = (Player, Action, KeyCode)> + '_ {
+        [Player::One, Player::Two]
+            .into_iter()
+            .flat_map(move |player| {
+                Action::ALL
+                    .into_iter()
+                    .map(move |action| (player, action, self.player(player).key(action)))
+            })
+    }
+}
+
+fn key_name(key: KeyCode) -> String {
+    let name = format!("{key:?}");
+    name.strip_prefix("Key").unwrap_or(&name).to_string()
+}
+
+#[derive(Component)]
+struct ControlsScreen {
+    // the binding waiting for a new key, if any
+    listening: Option<(Player, Action)>,
+}
+
+pub struct ControlsPlugin;
+
+impl Plugin for ControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                toggle_controls_screen,
+                remap_controls,
+                update_controls_screen,
+            )
+                .chain(),
+        );
+    }
+}
+
+fn toggle_controls_screen(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    screen: Query<(Entity, &ControlsScreen)>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::KeyK) {
+        return;
+    }
+
+    match screen.get_single() {
+        // while listening K is reported as reserved rather than closing the screen
+        Ok((_, ControlsScreen { listening: Some(_) })) => {}
+        Ok((ent, _)) => commands.entity(ent).despawn(),
+        Err(_) => {
+            // centred below the HUD rather than over it
+            commands.spawn((
+                Text::default(),
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Val::Percent(15.0),
+                    width: Val::Percent(100.0),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                TextLayout::new_with_justify(JustifyText::Center),
+                BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+                ControlsScreen { listening: None },
+                Menu,
+            ));
+        }
+    }
+}
+
+fn remap_controls(
+    mut commands: Commands,
+    locale: Res<Locale>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut progress: ResMut<Progress>,
+    mut screen: Query<(Entity, &mut ControlsScreen)>,
+    menus: Query<Entity, With<Menu>>,
+) {
+    let Ok((ent, mut screen)) = screen.get_single_mut() else {
+        return;
+    };
+    if !only_menu(ent, &menus) {
+        return;
+    }
+
+    let Some((player, action)) = screen.listening else {
+        if keyboard_input.just_pressed(KeyCode::Backspace) {
+            progress.controls = Controls::default();
+            progress.save();
+            show_toast(&mut commands, locale.text("toast-controls-reset"));
+            return;
+        }
+        let bindings: Vec<(Player, Action, KeyCode)> = progress.controls.bindings().collect();
+        if let Some((_, (player, action, _))) = ACTION_KEYS
+            .iter()
+            .zip(bindings)
+            .find(|(key, _)| keyboard_input.just_pressed(**key))
+        {
+            screen.listening = Some((player, action));
+        }
+        return;
+    };
+
+    let Some(key) = keyboard_input.get_just_pressed().next().copied() else {
+        return;
+    };
+    screen.listening = None;
+    if key == KeyCode::Escape {
+        return;
+    }
+    if is_reserved(key) {
+        show_toast(
+            &mut commands,
+            locale.format("toast-key-reserved", &[("key", &key_name(key))]),
+        );
+        return;
+    }
+    if let Some((other_player, other_action, _)) = progress
+        .controls
+        .bindings()
+        .find(|(p, a, bound)| *bound == key && (*p, *a) != (player, action))
+    {
+        show_toast(
+            &mut commands,
+            locale.format(
+                "toast-key-conflict",
+                &[
+                    ("key", &key_name(key)),
+                    ("player", &other_player.name(&locale)),
+                    ("action", &other_action.name(&locale)),
+                ],
+            ),
+        );
+        return;
+    }
+
+    *progress.controls.player_mut(player).key_mut(action) = key;
+    progress.save();
+}
+
+fn update_controls_screen(
+    locale: Res<Locale>,
+    progress: Res<Progress>,
+    mut screen: Query<(&mut Text, &ControlsScreen)>,
+) {
+    for (mut text, screen) in screen.iter_mut() {
+        let mut lines = vec![locale.text("controls-title").to_string()];
+        for (i, (player, action, key)) in progress.controls.bindings().enumerate() {
+            let key = if screen.listening == Some((player, action)) {
+                locale.text("controls-listening").to_string()
+            } else {
+                key_name(key)
+            };
+            lines.push(format!(
+                "{}. {} {}: {key}",
+                (i + 1) % 10,
+                player.name(&locale),
+                action.name(&locale)
+            ));
+        }
+        text.0 = lines.join("\n");
+    }
+}
@@ -0,0 +1,106 @@
+use bevy::prelude::*;
+use std::{
+    collections::VecDeque,
+    fs,
+    panic::{self, PanicHookInfo},
+    path::PathBuf,
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{event_feed::FeedEvent, profiles::base_dir};
+
+const RECENT_EVENTS: usize = 100;
+const REPORT_DIR: &str = "crash-reports";
+
+// What the panic hook needs, kept up to date while the game runs since the
+// world can't be trusted once something has panicked.
+struct CrashState {
+    // the latest snapshot of the run and the autosave file it belongs in
+    run: Option<(PathBuf, String)>,
+    recent: VecDeque<String>,
+}
+
+static CRASH_STATE: Mutex<CrashState> = Mutex::new(CrashState {
+    run: None,
+    recent: VecDeque::new(),
+});
+
+pub fn stash_run(path: PathBuf, contents: String) {
+    if let Ok(mut state) = CRASH_STATE.lock() {
+        state.run = Some((path, contents));
+    }
+}
+
+pub struct CrashReportPlugin;
+
+impl Plugin for CrashReportPlugin {
+    fn build(&self, app: &mut App) {
+        install_panic_hook();
+        app.add_systems(Update, remember_events);
+    }
+}
+
+fn remember_events(time: Res<Time<Real>>, mut feed_reader: EventReader<FeedEvent>) {
+    let Ok(mut state) = CRASH_STATE.lock() else {
+        return;
+    };
+    for FeedEvent(line) in feed_reader.read() {
+        if state.recent.len() == RECENT_EVENTS {
+            state.recent.pop_front();
+        }
+        state
+            .recent
+            .push_back(format!("[{:.2}] {line}", time.elapsed_secs()));
+    }
+}
+
+fn install_panic_hook() {
+    let config = [
+        format!("version: {}", env!("CARGO_PKG_VERSION")),
+        format!("build: {}", env!("BUILD_HASH")),
+        format!("bevy: {}", env!("BEVY_VERSION")),
+        format!("os: {} {}", std::env::consts::OS, std::env::consts::ARCH),
+        format!("args: {:?}", std::env::args().skip(1).collect::<Vec<_>>()),
+    ]
+    .join("\n");
+
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        write_crash_files(&config, info);
+        previous(info);
+    }));
+}
+
+// Best effort only, a second panic in here would lose the original message.
+fn write_crash_files(config: &str, info: &PanicHookInfo) {
+    // the panic may have happened while the state was locked
+    let Ok(state) = CRASH_STATE.try_lock() else {
+        return;
+    };
+
+    // written over the next autosave slot so the recovery prompt offers it
+    if let Some((path, contents)) = &state.run {
+        let _ = fs::write(path, contents);
+    }
+
+    let Some(dir) = base_dir().map(|dir| dir.join(REPORT_DIR)) else {
+        return;
+    };
+    let crashed_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    let path = dir.join(format!("crash-{crashed_at}.txt"));
+    let events: Vec<&str> = state.recent.iter().map(String::as_str).collect();
+    let report = format!(
+        "{config}\n\n{info}\n\nlast {} events:\n{}\n",
+        events.len(),
+        events.join("\n")
+    );
+    if fs::create_dir_all(&dir)
+        .and_then(|_| fs::write(&path, report))
+        .is_ok()
+    {
+        eprintln!("Crash report written to {}", path.display());
+    }
+}
@@ -0,0 +1,104 @@
+use bevy::{prelude::*, window::PrimaryWindow};
+
+use crate::{locale::Locale, Menu};
+
+const SCROLL_SPEED: f32 = 30.0;
+
+const CONTRIBUTORS: [&str; 1] = ["Kieran Sweeden"];
+
+// (what, licence) for everything bundled that isn't ours.
+const ASSET_LICENSES: [(&str, &str); 2] = [
+    ("Bevy engine", "MIT or Apache-2.0"),
+    (
+        "Fira Mono (Bevy's default font)",
+        "SIL Open Font License 1.1",
+    ),
+];
+
+#[derive(Component)]
+struct Credits;
+
+pub struct CreditsPlugin;
+
+impl Plugin for CreditsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (toggle_credits, scroll_credits).chain());
+    }
+}
+
+fn credits_text(locale: &Locale) -> String {
+    let mut lines = vec![
+        locale.text("credits-title").to_string(),
+        String::new(),
+        locale.text("credits-contributors").to_string(),
+    ];
+    lines.extend(CONTRIBUTORS.iter().map(|name| name.to_string()));
+    lines.push(String::new());
+    lines.push(locale.text("credits-licenses").to_string());
+    lines.extend(
+        ASSET_LICENSES
+            .iter()
+            .map(|(what, license)| format!("{what}: {license}")),
+    );
+    lines.push(String::new());
+    lines.push(locale.format(
+        "credits-version",
+        &[
+            ("version", &env!("CARGO_PKG_VERSION")),
+            ("build", &env!("BUILD_HASH")),
+            ("bevy", &env!("BEVY_VERSION")),
+        ],
+    ));
+    lines.push(String::new());
+    lines.push(locale.text("credits-close").to_string());
+    lines.join("\n")
+}
+
+fn toggle_credits(
+    mut commands: Commands,
+    locale: Res<Locale>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    credits: Query<Entity, With<Credits>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::F1) {
+        return;
+    }
+
+    match credits.get_single() {
+        Ok(ent) => commands.entity(ent).despawn(),
+        Err(_) => {
+            commands.spawn((
+                Text::new(credits_text(&locale)),
+                TextLayout::new_with_justify(JustifyText::Center),
+                Node {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    top: Val::Px(window.height()),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+                Credits,
+                Menu,
+            ));
+        }
+    }
+}
+
+// Rolls the credits up the screen, starting again from the bottom once gone.
+fn scroll_credits(
+    time: Res<Time>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    mut credits: Query<(&mut Node, &ComputedNode), With<Credits>>,
+) {
+    for (mut node, computed) in credits.iter_mut() {
+        let Val::Px(top) = node.top else {
+            continue;
+        };
+        let mut top = top - SCROLL_SPEED * time.delta_secs();
+        if top < -computed.size().y * computed.inverse_scale_factor() {
+            top = window.height();
+        }
+        node.top = Val::Px(top);
+    }
+}
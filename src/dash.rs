@@ -0,0 +1,118 @@
+use bevy::prelude::*;
+
+use crate::{
+    gamepad, progress::Progress, snake_movement, Direction, FixedTimer, Position, SnakeHead,
+};
+
+// Energy it takes to fill the meter, a pellet gives `amount` from pickups.ron.
+pub const DASH_CHARGE: u32 = 3;
+// Cells covered in one dash, fewer if a wall comes first.
+const DASH_CELLS: u32 = 3;
+const DASH_COOLDOWN_TICKS: u32 = 10;
+// Two presses of the same movement key this close together dash.
+const DOUBLE_TAP_SECS: f32 = 0.25;
+const DASH_BUTTON: GamepadButton = GamepadButton::East;
+
+// Dash meter filled by energy pellets, lives on the head entity.
+#[derive(Component, Default)]
+pub struct Dash {
+    pub charge: u32,
+    pub cooldown: u32,
+    // set by input, spent on the next movement tick
+    queued: bool,
+}
+
+impl Dash {
+    pub fn restored(charge: u32, cooldown: u32) -> Self {
+        Self {
+            charge,
+            cooldown,
+            queued: false,
+        }
+    }
+
+    pub fn ready(&self) -> bool {
+        self.charge >= DASH_CHARGE && self.cooldown == 0
+    }
+
+    pub fn add_charge(&mut self, amount: u32) {
+        self.charge = (self.charge + amount).min(DASH_CHARGE);
+    }
+
+    // Cells the head moves this tick from `from`: one normally, up to
+    // DASH_CELLS when a dash is queued. Walls stop a dash short, anything
+    // else in between is passed over.
+    pub fn steps(&mut self, from: Position, direction: Direction) -> u32 {
+        if !std::mem::take(&mut self.queued) {
+            return 1;
+        }
+        self.charge = 0;
+        self.cooldown = DASH_COOLDOWN_TICKS;
+
+        let mut pos = from;
+        let mut steps = 0;
+        while steps < DASH_CELLS && pos.moved(direction).in_arena() {
+            pos = pos.moved(direction);
+            steps += 1;
+        }
+        steps.max(1)
+    }
+}
+
+pub struct DashPlugin;
+
+impl Plugin for DashPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                dash_input.before(snake_movement),
+                cool_down.after(snake_movement),
+            ),
+        );
+    }
+}
+
+fn dash_input(
+    real_time: Res<Time<Real>>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    progress: Res<Progress>,
+    gamepads: Query<(Entity, &Gamepad)>,
+    mut last_taps: Local<Vec<(Entity, KeyCode, f32)>>,
+    mut heads: Query<(Entity, &SnakeHead, &mut Dash)>,
+) {
+    let now = real_time.elapsed_secs();
+    for (head_entity, head, mut dash) in heads.iter_mut() {
+        let tapped = progress
+            .controls
+            .player(head.player)
+            .directions()
+            .into_iter()
+            .map(|(key, _)| key)
+            .find(|key| keyboard_input.just_pressed(*key));
+        let mut pressed = false;
+        if let Some(key) = tapped {
+            pressed = last_taps.iter().any(|(ent, last_key, at)| {
+                *ent == head_entity && *last_key == key && now - at <= DOUBLE_TAP_SECS
+            });
+            last_taps.retain(|(ent, ..)| *ent != head_entity);
+            last_taps.push((head_entity, key, now));
+        }
+        pressed |= gamepad::player_gamepad(head.player, &gamepads)
+            .is_some_and(|(_, gamepad)| gamepad.just_pressed(DASH_BUTTON));
+
+        if pressed && dash.ready() {
+            dash.queued = true;
+        }
+    }
+}
+
+fn cool_down(timer: Res<FixedTimer>, mut dashes: Query<&mut Dash>) {
+    if !timer.0.just_finished() {
+        return;
+    }
+
+    for mut dash in dashes.iter_mut() {
+        dash.cooldown = dash.cooldown.saturating_sub(1);
+    }
+}
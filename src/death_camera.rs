@@ -0,0 +1,73 @@
+use bevy::{prelude::*, window::PrimaryWindow};
+
+use crate::{
+    cell_center, progress::Progress, sim_speed::SimSpeed, tail_camera::MainCamera, GameOverEvent,
+    Position,
+};
+
+const DEATH_CAMERA_SECS: f32 = 1.2;
+// share of the sequence spent closing in, the rest holds on the cell
+const DEATH_CAMERA_ZOOM_IN: f32 = 0.4;
+const DEATH_CAMERA_SCALE: f32 = 0.5;
+const DEATH_SLOW_MOTION: f32 = 0.25;
+
+// The death that ended a run, played out in slow motion before the reset.
+#[derive(Resource, Default)]
+pub struct DeathCamera(Option<(Position, Timer)>);
+
+impl DeathCamera {
+    pub fn start(&mut self, position: Position) {
+        if self.0.is_none() {
+            self.0 = Some((
+                position,
+                Timer::from_seconds(DEATH_CAMERA_SECS, TimerMode::Once),
+            ));
+        }
+    }
+}
+
+// Run condition for gameplay, which stands still while the camera plays.
+pub fn idle(death_camera: Res<DeathCamera>) -> bool {
+    death_camera.0.is_none()
+}
+
+pub struct DeathCameraPlugin;
+
+impl Plugin for DeathCameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(DeathCamera::default())
+            .add_systems(Update, play_death_camera);
+    }
+}
+
+fn play_death_camera(
+    time: Res<Time<Real>>,
+    progress: Res<Progress>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    mut death_camera: ResMut<DeathCamera>,
+    mut speed: ResMut<SimSpeed>,
+    mut camera: Single<(&mut Transform, &mut OrthographicProjection), MainCamera>,
+    mut game_over_writer: EventWriter<GameOverEvent>,
+) {
+    let Some((position, timer)) = death_camera.0.as_mut() else {
+        return;
+    };
+    let (transform, projection) = &mut *camera;
+
+    // cut straight back to the reset arena once it's over
+    if timer.tick(time.delta()).finished() {
+        transform.translation = Vec3::ZERO;
+        projection.scale = 1.0;
+        speed.set_slow_motion(1.0);
+        death_camera.0 = None;
+        game_over_writer.send(GameOverEvent);
+        return;
+    }
+
+    speed.set_slow_motion(DEATH_SLOW_MOTION);
+    let t = (timer.fraction() / DEATH_CAMERA_ZOOM_IN).min(1.0);
+    let eased = t * t * (3.0 - 2.0 * t);
+    transform.translation =
+        (cell_center(*position, progress.orientation, &window) * eased).extend(0.0);
+    projection.scale = 1.0 - (1.0 - DEATH_CAMERA_SCALE) * eased;
+}
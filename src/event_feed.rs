@@ -0,0 +1,161 @@
+use bevy::prelude::*;
+
+use crate::{
+    locale::Locale, projectile_hits, snake_death, GrowthEvent, PowerUpPickedEvent, SeveredEvent,
+    Shield, SnakeDiedEvent, SnakeHead, SnakeSegments,
+};
+
+const FEED_LENGTH: usize = 5;
+const FEED_ENTRY_SECS: f32 = 4.0;
+// entries fade out over the end of their time
+const FEED_FADE_SECS: f32 = 1.0;
+
+// A line for the event feed. Gameplay is translated into these here, and
+// anything else, like script mods, can send its own.
+#[derive(Event)]
+pub struct FeedEvent(pub String);
+
+#[derive(Component)]
+struct EventFeed;
+
+#[derive(Component)]
+struct FeedEntry(Timer);
+
+pub struct EventFeedPlugin;
+
+impl Plugin for EventFeedPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<FeedEvent>()
+            .add_systems(Startup, spawn_event_feed)
+            .add_systems(
+                Update,
+                (
+                    feed_pickups,
+                    feed_collisions.after(projectile_hits).before(snake_death),
+                    (add_feed_entries, fade_feed_entries).chain(),
+                ),
+            );
+    }
+}
+
+// Down the right-hand side, clear of the mission board in the corner above.
+fn spawn_event_feed(mut commands: Commands) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Percent(40.0),
+            right: Val::Px(5.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::FlexEnd,
+            ..default()
+        },
+        EventFeed,
+    ));
+}
+
+fn feed_pickups(
+    locale: Res<Locale>,
+    mut growth_reader: EventReader<GrowthEvent>,
+    mut picked_reader: EventReader<PowerUpPickedEvent>,
+    heads: Query<(&SnakeHead, &SnakeSegments)>,
+    mut feed_writer: EventWriter<FeedEvent>,
+) {
+    for GrowthEvent(head) in growth_reader.read() {
+        if let Ok((head, segments)) = heads.get(*head) {
+            feed_writer.send(FeedEvent(locale.format(
+                "feed-food",
+                &[
+                    ("player", &head.player.name(&locale)),
+                    ("length", &segments.0.len()),
+                ],
+            )));
+        }
+    }
+    for PowerUpPickedEvent(head, power_up) in picked_reader.read() {
+        if let Ok((head, _)) = heads.get(*head) {
+            feed_writer.send(FeedEvent(locale.format(
+                "feed-power-up",
+                &[
+                    ("player", &head.player.name(&locale)),
+                    ("power_up", &power_up.name(&locale)),
+                ],
+            )));
+        }
+    }
+}
+
+// Runs after every collision check and before snake_death, so the dying
+// snake is still around to be named.
+fn feed_collisions(
+    locale: Res<Locale>,
+    mut severed_reader: EventReader<SeveredEvent>,
+    mut died_reader: EventReader<SnakeDiedEvent>,
+    mut removed_shields: RemovedComponents<Shield>,
+    heads: Query<(&SnakeHead, Has<Shield>)>,
+    mut feed_writer: EventWriter<FeedEvent>,
+) {
+    let mut lines = Vec::new();
+    for SeveredEvent(head) in severed_reader.read() {
+        lines.push((*head, "feed-severed"));
+    }
+    // a shielded snake survives, the popped shield is reported instead
+    let mut crashed = Vec::new();
+    for SnakeDiedEvent(head) in died_reader.read() {
+        if !crashed.contains(head) && heads.get(*head).is_ok_and(|(_, shielded)| !shielded) {
+            crashed.push(*head);
+            lines.push((*head, "feed-crashed"));
+        }
+    }
+    // shields are only removed when they pop, a despawned snake isn't found
+    for head in removed_shields.read() {
+        lines.push((head, "feed-shield"));
+    }
+
+    for (head, key) in lines {
+        if let Ok((head, _)) = heads.get(head) {
+            feed_writer.send(FeedEvent(
+                locale.format(key, &[("player", &head.player.name(&locale))]),
+            ));
+        }
+    }
+}
+
+fn add_feed_entries(
+    mut commands: Commands,
+    mut feed_reader: EventReader<FeedEvent>,
+    feed: Single<(Entity, Option<&Children>), With<EventFeed>>,
+) {
+    let (feed, entries) = *feed;
+    let mut entries: Vec<Entity> = entries.map_or_else(Vec::new, |c| c.to_vec());
+    for FeedEvent(line) in feed_reader.read() {
+        let entry = commands
+            .spawn((
+                Text::new(line.clone()),
+                FeedEntry(Timer::from_seconds(FEED_ENTRY_SECS, TimerMode::Once)),
+            ))
+            .id();
+        commands.entity(feed).add_child(entry);
+        entries.push(entry);
+    }
+
+    // only the newest few are kept
+    let excess = entries.len().saturating_sub(FEED_LENGTH);
+    for entry in &entries[..excess] {
+        commands.entity(*entry).despawn_recursive();
+    }
+}
+
+fn fade_feed_entries(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut entries: Query<(Entity, &mut FeedEntry, &mut TextColor)>,
+) {
+    for (ent, mut entry, mut color) in entries.iter_mut() {
+        if entry.0.tick(time.delta()).finished() {
+            commands.entity(ent).despawn_recursive();
+            continue;
+        }
+        let remaining = entry.0.remaining_secs();
+        color.0.set_alpha((remaining / FEED_FADE_SECS).min(1.0));
+    }
+}
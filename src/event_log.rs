@@ -0,0 +1,204 @@
+use bevy::prelude::*;
+use serde::Serialize;
+use std::{
+    fs::{self, File},
+    io::{LineWriter, Write},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    profiles::base_dir, projectile_hits, snake_death, snake_movement, FixedTimer, GameMode,
+    GameOverEvent, GrowthEvent, Player, Position, PowerUp, PowerUpPickedEvent,
+    ReversalAttemptEvent, SeveredEvent, Shield, SnakeDiedEvent, SnakeHead, SnakeSegments,
+};
+
+const LOG_DIR: &str = "event-logs";
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum LoggedEvent {
+    RunStarted {
+        mode: GameMode,
+    },
+    Grew {
+        player: Player,
+        length: usize,
+    },
+    PowerUp {
+        player: Player,
+        power_up: PowerUp,
+    },
+    Severed {
+        player: Player,
+        length: usize,
+    },
+    Died {
+        player: Player,
+        position: Position,
+        shielded: bool,
+    },
+    ReversalAttempt {
+        player: Player,
+    },
+    GameOver,
+}
+
+#[derive(Serialize)]
+struct LogLine<'a> {
+    tick: u64,
+    #[serde(flatten)]
+    event: &'a LoggedEvent,
+}
+
+// With `--log-events`, every gameplay event is written as a JSON line to a
+// new file per run, stamped with the movement tick it happened on.
+#[derive(Resource)]
+struct EventLog {
+    tick: u64,
+    file: Option<LineWriter<File>>,
+}
+
+impl EventLog {
+    fn start_run(&mut self, mode: GameMode) {
+        self.tick = 0;
+        self.file = Self::create_file();
+        self.write(&LoggedEvent::RunStarted { mode });
+    }
+
+    fn create_file() -> Option<LineWriter<File>> {
+        let dir = base_dir()?.join(LOG_DIR);
+        let started = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_millis());
+        let path = dir.join(format!("run-{started}.jsonl"));
+        match fs::create_dir_all(&dir).and_then(|_| File::create(&path)) {
+            Ok(file) => {
+                info!("Logging events to {}", path.display());
+                Some(LineWriter::new(file))
+            }
+            Err(err) => {
+                warn!("Failed to create event log {}: {err}", path.display());
+                None
+            }
+        }
+    }
+
+    fn write(&mut self, event: &LoggedEvent) {
+        let Some(file) = self.file.as_mut() else {
+            return;
+        };
+        let line = LogLine {
+            tick: self.tick,
+            event,
+        };
+        let result = serde_json::to_string(&line)
+            .map_err(std::io::Error::from)
+            .and_then(|json| writeln!(file, "{json}"));
+        // one failure is enough, don't warn on every event after it
+        if let Err(err) = result {
+            warn!("Stopped logging events: {err}");
+            self.file = None;
+        }
+    }
+}
+
+pub struct EventLogPlugin;
+
+impl Plugin for EventLogPlugin {
+    fn build(&self, app: &mut App) {
+        if !std::env::args().any(|arg| arg == "--log-events") {
+            return;
+        }
+
+        app.insert_resource(EventLog {
+            tick: 0,
+            file: None,
+        })
+        .add_systems(Startup, start_event_log)
+        .add_systems(
+            Update,
+            (
+                count_ticks.after(snake_movement),
+                log_events.after(projectile_hits).before(snake_death),
+                rotate_event_log.after(log_events),
+            ),
+        );
+    }
+}
+
+fn start_event_log(mode: Res<GameMode>, mut log: ResMut<EventLog>) {
+    log.start_run(*mode);
+}
+
+fn count_ticks(timer: Res<FixedTimer>, mut log: ResMut<EventLog>) {
+    if timer.0.just_finished() {
+        log.tick += 1;
+    }
+}
+
+fn log_events(
+    mut log: ResMut<EventLog>,
+    mut growth_reader: EventReader<GrowthEvent>,
+    mut picked_reader: EventReader<PowerUpPickedEvent>,
+    mut severed_reader: EventReader<SeveredEvent>,
+    mut died_reader: EventReader<SnakeDiedEvent>,
+    mut reversal_reader: EventReader<ReversalAttemptEvent>,
+    heads: Query<(&SnakeHead, &SnakeSegments, &Position, Has<Shield>)>,
+) {
+    let mut events = Vec::new();
+    for GrowthEvent(head) in growth_reader.read() {
+        if let Ok((head, segments, ..)) = heads.get(*head) {
+            events.push(LoggedEvent::Grew {
+                player: head.player,
+                length: segments.0.len(),
+            });
+        }
+    }
+    for PowerUpPickedEvent(head, power_up) in picked_reader.read() {
+        if let Ok((head, ..)) = heads.get(*head) {
+            events.push(LoggedEvent::PowerUp {
+                player: head.player,
+                power_up: *power_up,
+            });
+        }
+    }
+    for SeveredEvent(head) in severed_reader.read() {
+        if let Ok((head, segments, ..)) = heads.get(*head) {
+            events.push(LoggedEvent::Severed {
+                player: head.player,
+                length: segments.0.len(),
+            });
+        }
+    }
+    for SnakeDiedEvent(head) in died_reader.read() {
+        if let Ok((head, _, position, shielded)) = heads.get(*head) {
+            events.push(LoggedEvent::Died {
+                player: head.player,
+                position: *position,
+                shielded,
+            });
+        }
+    }
+    for ReversalAttemptEvent(head) in reversal_reader.read() {
+        if let Ok((head, ..)) = heads.get(*head) {
+            events.push(LoggedEvent::ReversalAttempt {
+                player: head.player,
+            });
+        }
+    }
+    for event in &events {
+        log.write(event);
+    }
+}
+
+// Each run gets its own file.
+fn rotate_event_log(
+    mode: Res<GameMode>,
+    mut log: ResMut<EventLog>,
+    mut game_over_reader: EventReader<GameOverEvent>,
+) {
+    if game_over_reader.read().next().is_some() {
+        log.write(&LoggedEvent::GameOver);
+        log.start_run(*mode);
+    }
+}
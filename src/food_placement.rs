@@ -0,0 +1,115 @@
+use bevy::prelude::*;
+use rand::Rng;
+use serde::Deserialize;
+
+use crate::{
+    pickups::Pickups, snake_movement, FixedTimer, GameMode, GameOverEvent, GameRng, Position,
+    SnakeHead, ARENA_HEIGHT, ARENA_WIDTH,
+};
+
+// How food picks among the free cells, set per mode in assets/pickups.ron.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum FoodStrategy {
+    #[default]
+    Uniform,
+    // favours cells the snakes have rarely been through this run, so long
+    // games are pulled out of whatever corner they've settled in
+    Unvisited,
+}
+
+// How many ticks a snake head has spent on each cell this run, along with
+// the strategy in use for the current mode.
+#[derive(Resource)]
+pub struct FoodPlacement {
+    strategy: FoodStrategy,
+    visits: Vec<u32>,
+}
+
+impl FoodPlacement {
+    fn index(pos: Position) -> Option<usize> {
+        pos.in_arena()
+            .then(|| pos.y as usize * ARENA_WIDTH as usize + pos.x as usize)
+    }
+
+    fn visits(&self, pos: Position) -> u32 {
+        Self::index(pos).map_or(0, |i| self.visits[i])
+    }
+
+    // Index into `free` of the cell the next food goes on.
+    pub fn pick(&self, free: &[Position], rng: &mut GameRng) -> usize {
+        if self.strategy == FoodStrategy::Uniform {
+            return rng.0.gen_range(0..free.len());
+        }
+
+        // the least visited cell is the likeliest, the most visited still
+        // has some chance
+        let most = free.iter().map(|pos| self.visits(*pos)).max().unwrap_or(0);
+        let weights: Vec<u32> = free
+            .iter()
+            .map(|pos| most + 1 - self.visits(*pos))
+            .collect();
+        let mut roll = rng.0.gen_range(0..weights.iter().sum::<u32>());
+        weights
+            .iter()
+            .position(|weight| {
+                if roll < *weight {
+                    true
+                } else {
+                    roll -= weight;
+                    false
+                }
+            })
+            .unwrap_or(0)
+    }
+}
+
+pub struct FoodPlacementPlugin;
+
+impl Plugin for FoodPlacementPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(FoodPlacement {
+            strategy: FoodStrategy::Uniform,
+            visits: vec![0; (ARENA_WIDTH * ARENA_HEIGHT) as usize],
+        })
+        .add_systems(
+            Update,
+            (
+                apply_strategy,
+                count_visits.after(snake_movement),
+                forget_visits,
+            ),
+        );
+    }
+}
+
+fn apply_strategy(
+    mode: Res<GameMode>,
+    pickups: Res<Pickups>,
+    mut placement: ResMut<FoodPlacement>,
+) {
+    if mode.is_changed() || pickups.is_changed() {
+        placement.strategy = pickups.food_strategy(*mode);
+    }
+}
+
+fn count_visits(
+    timer: Res<FixedTimer>,
+    mut placement: ResMut<FoodPlacement>,
+    heads: Query<&Position, With<SnakeHead>>,
+) {
+    if !timer.0.just_finished() {
+        return;
+    }
+
+    for pos in heads.iter() {
+        if let Some(i) = FoodPlacement::index(*pos) {
+            placement.visits[i] += 1;
+        }
+    }
+}
+
+fn forget_visits(mut reader: EventReader<GameOverEvent>, mut placement: ResMut<FoodPlacement>) {
+    if reader.read().next().is_some() {
+        placement.visits.fill(0);
+    }
+}
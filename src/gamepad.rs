@@ -0,0 +1,87 @@
+use bevy::{
+    input::gamepad::{GamepadRumbleIntensity, GamepadRumbleRequest},
+    prelude::*,
+};
+use std::time::Duration;
+
+use crate::{
+    progress::Progress, projectile_hits, snake_death, Direction, GrowthEvent, Player,
+    PowerUpPickedEvent, SnakeDiedEvent, SnakeHead,
+};
+
+pub const DPAD: [(GamepadButton, Direction); 4] = [
+    (GamepadButton::DPadLeft, Direction::Left),
+    (GamepadButton::DPadDown, Direction::Down),
+    (GamepadButton::DPadUp, Direction::Up),
+    (GamepadButton::DPadRight, Direction::Right),
+];
+pub const FIRE_BUTTON: GamepadButton = GamepadButton::South;
+
+// Gamepads are handed to players in the order they were connected.
+pub fn player_gamepad<'a>(
+    player: Player,
+    gamepads: &'a Query<(Entity, &Gamepad)>,
+) -> Option<(Entity, &'a Gamepad)> {
+    let mut connected: Vec<(Entity, &Gamepad)> = gamepads.iter().collect();
+    connected.sort_by_key(|(ent, _)| *ent);
+    connected.get(player as usize).copied()
+}
+
+pub struct GamepadPlugin;
+
+impl Plugin for GamepadPlugin {
+    fn build(&self, app: &mut App) {
+        // after every collision check has sent its deaths, and before they're
+        // handled, while the dying snake's head still exists
+        app.add_systems(Update, rumble.after(projectile_hits).before(snake_death));
+    }
+}
+
+fn rumble(
+    progress: Res<Progress>,
+    mut growth_reader: EventReader<GrowthEvent>,
+    mut picked_reader: EventReader<PowerUpPickedEvent>,
+    mut died_reader: EventReader<SnakeDiedEvent>,
+    heads: Query<&SnakeHead>,
+    gamepads: Query<(Entity, &Gamepad)>,
+    mut rumble_writer: EventWriter<GamepadRumbleRequest>,
+) {
+    // (head, strength, millis) for everything that happened this frame
+    let pulses: Vec<(Entity, f32, u64)> = growth_reader
+        .read()
+        .map(|GrowthEvent(head)| (*head, 0.3, 80))
+        .chain(
+            picked_reader
+                .read()
+                .map(|PowerUpPickedEvent(head, _)| (*head, 0.5, 150)),
+        )
+        .chain(
+            died_reader
+                .read()
+                .map(|SnakeDiedEvent(head)| (*head, 1.0, 400)),
+        )
+        .collect();
+    if progress.rumble <= 0.0 {
+        return;
+    }
+
+    for (head, strength, millis) in pulses {
+        let Some(gamepad) = heads
+            .get(head)
+            .ok()
+            .and_then(|head| player_gamepad(head.player, &gamepads))
+            .map(|(gamepad, _)| gamepad)
+        else {
+            continue;
+        };
+        let strength = (strength * progress.rumble).min(1.0);
+        rumble_writer.send(GamepadRumbleRequest::Add {
+            duration: Duration::from_millis(millis),
+            intensity: GamepadRumbleIntensity {
+                strong_motor: strength,
+                weak_motor: strength,
+            },
+            gamepad,
+        });
+    }
+}
@@ -0,0 +1,129 @@
+use bevy::prelude::*;
+use std::{
+    borrow::Cow,
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use crate::{
+    adaptive::AdaptiveConfig, locale::Locale, pickups::Pickups, seasons::Seasons,
+    world_events::WorldEvents,
+};
+
+const POLL_SECS: f32 = 0.5;
+const PICKUPS_FILE: &str = "pickups.ron";
+const ADAPTIVE_FILE: &str = "adaptive.ron";
+const SEASONS_FILE: &str = "seasons.ron";
+const WORLD_EVENTS_FILE: &str = "world_events.ron";
+const WATCHED: [&str; 6] = [
+    PICKUPS_FILE,
+    ADAPTIVE_FILE,
+    SEASONS_FILE,
+    WORLD_EVENTS_FILE,
+    "locales/en.ron",
+    "locales/es.ron",
+];
+
+// Embeds a file from assets/, which debug builds read from disk instead so
+// edits show up without a rebuild.
+macro_rules! data_file {
+    ($name:literal) => {
+        $crate::hot_reload::read(
+            $name,
+            include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/assets/", $name)),
+        )
+    };
+}
+pub(crate) use data_file;
+
+pub fn read(name: &str, embedded: &'static str) -> Cow<'static, str> {
+    if cfg!(debug_assertions) {
+        if let Ok(contents) = fs::read_to_string(path(name)) {
+            return Cow::Owned(contents);
+        }
+    }
+    Cow::Borrowed(embedded)
+}
+
+fn path(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("assets")
+        .join(name)
+}
+
+fn modified(name: &str) -> Option<SystemTime> {
+    fs::metadata(path(name))
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+#[derive(Resource)]
+struct Watcher {
+    timer: Timer,
+    modified: Vec<Option<SystemTime>>,
+}
+
+pub struct HotReloadPlugin;
+
+impl Plugin for HotReloadPlugin {
+    fn build(&self, app: &mut App) {
+        if !cfg!(debug_assertions) {
+            return;
+        }
+
+        app.insert_resource(Watcher {
+            timer: Timer::from_seconds(POLL_SECS, TimerMode::Repeating),
+            modified: WATCHED.iter().map(|name| modified(name)).collect(),
+        })
+        .add_systems(Update, reload_changed_files);
+    }
+}
+
+fn reload_changed_files(
+    time: Res<Time<Real>>,
+    mut watcher: ResMut<Watcher>,
+    mut locale: ResMut<Locale>,
+    mut pickups: ResMut<Pickups>,
+    mut adaptive: ResMut<AdaptiveConfig>,
+    mut seasons: ResMut<Seasons>,
+    mut world_events: ResMut<WorldEvents>,
+) {
+    if !watcher.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    for (name, last) in WATCHED.iter().zip(watcher.modified.iter_mut()) {
+        let now = modified(name);
+        if now == *last {
+            continue;
+        }
+        *last = now;
+
+        info!("Reloading {name}");
+        if *name == PICKUPS_FILE {
+            // a half-saved file keeps the old definitions rather than crashing
+            match Pickups::parse(&data_file!("pickups.ron")) {
+                Ok(reloaded) => *pickups = reloaded,
+                Err(err) => warn!("Keeping old pickup definitions: {err}"),
+            }
+        } else if *name == ADAPTIVE_FILE {
+            match AdaptiveConfig::parse(&data_file!("adaptive.ron")) {
+                Ok(reloaded) => *adaptive = reloaded,
+                Err(err) => warn!("Keeping old adaptive difficulty config: {err}"),
+            }
+        } else if *name == SEASONS_FILE {
+            match Seasons::parse(&data_file!("seasons.ron")) {
+                Ok(reloaded) => *seasons = reloaded,
+                Err(err) => warn!("Keeping old seasonal events: {err}"),
+            }
+        } else if *name == WORLD_EVENTS_FILE {
+            match WorldEvents::parse(&data_file!("world_events.ron")) {
+                Ok(reloaded) => *world_events = reloaded,
+                Err(err) => warn!("Keeping old world events: {err}"),
+            }
+        } else {
+            locale.reload();
+        }
+    }
+}
@@ -0,0 +1,222 @@
+use bevy::prelude::*;
+
+use crate::{
+    gamepad, locale::Locale, progress::Progress, snake_movement, snake_movement_input, Direction,
+    FixedTimer, ReversalAttemptEvent, SnakeHead,
+};
+
+// Width of each histogram bucket logged at exit.
+const BUCKET_MILLIS: u32 = 25;
+const BUCKETS: usize = 20;
+
+// A movement press waiting for the tick that applies it.
+struct Press {
+    head: Entity,
+    // the direction the head was left facing by the press
+    direction: Direction,
+    at_secs: f32,
+    at_frame: u32,
+}
+
+// Time from a movement press to the tick that moves the snake with it,
+// switched on with `--input-latency`. A press counts as dropped if it
+// was a refused reversal or another press replaced it before the tick.
+#[derive(Resource, Default)]
+struct InputLatency {
+    frame: u32,
+    pending: Vec<Press>,
+    // milliseconds and frames for every applied press
+    samples: Vec<(f32, u32)>,
+    dropped: u32,
+}
+
+impl InputLatency {
+    fn percentile(&self, share: f32) -> f32 {
+        let mut millis: Vec<f32> = self.samples.iter().map(|(ms, _)| *ms).collect();
+        millis.sort_by(f32::total_cmp);
+        let i = ((millis.len() as f32 * share) as usize).min(millis.len().saturating_sub(1));
+        millis.get(i).copied().unwrap_or(0.0)
+    }
+
+    fn histogram(&self) -> String {
+        let mut counts = [0u32; BUCKETS];
+        for (ms, _) in &self.samples {
+            let bucket = (*ms as u32 / BUCKET_MILLIS) as usize;
+            counts[bucket.min(BUCKETS - 1)] += 1;
+        }
+        let most = counts.iter().copied().max().unwrap_or(0).max(1);
+        counts
+            .iter()
+            .enumerate()
+            .filter(|(_, count)| **count > 0)
+            .map(|(i, count)| {
+                let from = i as u32 * BUCKET_MILLIS;
+                let label = if i == BUCKETS - 1 {
+                    format!("{from}+ ms")
+                } else {
+                    format!("{from}-{} ms", from + BUCKET_MILLIS)
+                };
+                let bar = "#".repeat((count * 40).div_ceil(most) as usize);
+                format!("{label:>12} {count:>5} {bar}")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[derive(Component)]
+struct LatencyReadout;
+
+pub struct InputLatencyPlugin;
+
+impl Plugin for InputLatencyPlugin {
+    fn build(&self, app: &mut App) {
+        if !std::env::args().any(|arg| arg == "--input-latency") {
+            return;
+        }
+
+        app.init_resource::<InputLatency>()
+            .add_systems(Startup, spawn_readout)
+            .add_systems(
+                Update,
+                (
+                    record_presses
+                        .after(snake_movement_input)
+                        .before(snake_movement),
+                    (
+                        measure_applied_presses.after(snake_movement),
+                        update_readout,
+                    )
+                        .chain(),
+                ),
+            )
+            .add_systems(Last, log_histogram);
+    }
+}
+
+// Top middle, between the HUD and the mission board.
+fn spawn_readout(mut commands: Commands) {
+    commands.spawn((
+        Text::default(),
+        TextFont::from_font_size(14.0),
+        TextLayout::new_with_justify(JustifyText::Center),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(5.0),
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        LatencyReadout,
+    ));
+}
+
+fn record_presses(
+    real_time: Res<Time<Real>>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    progress: Res<Progress>,
+    gamepads: Query<(Entity, &Gamepad)>,
+    mut latency: ResMut<InputLatency>,
+    mut reversal_reader: EventReader<ReversalAttemptEvent>,
+    heads: Query<(Entity, &SnakeHead)>,
+) {
+    latency.frame += 1;
+    let refused: Vec<Entity> = reversal_reader
+        .read()
+        .map(|ReversalAttemptEvent(head)| *head)
+        .collect();
+
+    for (head_entity, head) in heads.iter() {
+        let keys = progress.controls.player(head.player).directions();
+        let pressed = keys
+            .iter()
+            .any(|(key, _)| keyboard_input.just_pressed(*key))
+            || gamepad::player_gamepad(head.player, &gamepads).is_some_and(|(_, gamepad)| {
+                gamepad::DPAD
+                    .iter()
+                    .any(|(button, _)| gamepad.just_pressed(*button))
+            });
+        if !pressed {
+            continue;
+        }
+
+        if refused.contains(&head_entity) {
+            latency.dropped += 1;
+            continue;
+        }
+        let press = Press {
+            head: head_entity,
+            direction: head.direction,
+            at_secs: real_time.elapsed_secs(),
+            at_frame: latency.frame,
+        };
+        latency.pending.push(press);
+    }
+}
+
+fn measure_applied_presses(
+    real_time: Res<Time<Real>>,
+    timer: Res<FixedTimer>,
+    mut latency: ResMut<InputLatency>,
+    heads: Query<&SnakeHead>,
+) {
+    if !timer.0.just_finished() {
+        return;
+    }
+
+    let now = real_time.elapsed_secs();
+    let frame = latency.frame;
+    for press in std::mem::take(&mut latency.pending) {
+        // a snake that died meanwhile never got to use it
+        let applied = heads
+            .get(press.head)
+            .is_ok_and(|head| head.direction == press.direction);
+        if applied {
+            let millis = (now - press.at_secs) * 1000.0;
+            latency.samples.push((millis, frame - press.at_frame));
+        } else {
+            latency.dropped += 1;
+        }
+    }
+}
+
+fn update_readout(
+    locale: Res<Locale>,
+    latency: Res<InputLatency>,
+    mut readout: Single<&mut Text, With<LatencyReadout>>,
+) {
+    if !latency.is_changed() {
+        return;
+    }
+
+    let Some((last_ms, last_frames)) = latency.samples.last() else {
+        return;
+    };
+    let average =
+        latency.samples.iter().map(|(ms, _)| ms).sum::<f32>() / latency.samples.len() as f32;
+    readout.0 = locale.format(
+        "latency-readout",
+        &[
+            ("last", &format!("{last_ms:.0}")),
+            ("frames", last_frames),
+            ("average", &format!("{average:.0}")),
+            ("p95", &format!("{:.0}", latency.percentile(0.95))),
+            ("dropped", &latency.dropped),
+        ],
+    );
+}
+
+fn log_histogram(latency: Res<InputLatency>, mut exit_reader: EventReader<AppExit>) {
+    if exit_reader.read().next().is_none() {
+        return;
+    }
+
+    info!(
+        "Input latency over {} presses, {} dropped, p50 {:.0} ms, p95 {:.0} ms\n{}",
+        latency.samples.len(),
+        latency.dropped,
+        latency.percentile(0.5),
+        latency.percentile(0.95),
+        latency.histogram()
+    );
+}
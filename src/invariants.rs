@@ -0,0 +1,95 @@
+use bevy::prelude::*;
+use std::fmt::Write;
+
+use crate::{death_camera, tron_last_alive, FixedTimer, Position, SnakeHead, SnakeSegments};
+
+// Checked after every tick with the `debug-tools` feature, panicking with a
+// dump of every snake as soon as one breaks:
+// - every entity in SnakeSegments exists and has a position
+// - each segment is one cell away from the one before it
+// - no two segments of the same snake share a cell
+// - every head is inside the arena
+pub struct InvariantsPlugin;
+
+impl Plugin for InvariantsPlugin {
+    fn build(&self, app: &mut App) {
+        if !cfg!(feature = "debug-tools") {
+            return;
+        }
+
+        // the death camera holds the final, broken state on screen on purpose
+        app.add_systems(
+            Update,
+            check_invariants
+                .after(tron_last_alive)
+                .run_if(death_camera::idle),
+        );
+    }
+}
+
+fn check_invariants(
+    timer: Res<FixedTimer>,
+    heads: Query<(Entity, &SnakeHead, &SnakeSegments)>,
+    positions: Query<&Position>,
+) {
+    if !timer.0.just_finished() {
+        return;
+    }
+
+    for (head_entity, _, segments) in heads.iter() {
+        if let Err(problem) = check_snake(head_entity, segments, &positions) {
+            panic!("{problem}\n{}", dump(&heads, &positions));
+        }
+    }
+}
+
+fn check_snake(
+    head_entity: Entity,
+    segments: &SnakeSegments,
+    positions: &Query<&Position>,
+) -> Result<(), String> {
+    if segments.0.first() != Some(&head_entity) {
+        return Err(format!(
+            "{head_entity}'s segments don't start with its head"
+        ));
+    }
+
+    let mut cells: Vec<Position> = Vec::with_capacity(segments.0.len());
+    for ent in &segments.0 {
+        let Ok(pos) = positions.get(*ent) else {
+            return Err(format!("{head_entity} has a missing segment {ent}"));
+        };
+        if let Some(previous) = cells.last() {
+            if (previous.x - pos.x).abs() + (previous.y - pos.y).abs() != 1 {
+                return Err(format!("{head_entity} has a gap before segment {ent}"));
+            }
+        }
+        if cells.contains(pos) {
+            return Err(format!("{head_entity} overlaps itself at segment {ent}"));
+        }
+        cells.push(*pos);
+    }
+
+    if !cells[0].in_arena() {
+        return Err(format!("{head_entity}'s head is outside the arena"));
+    }
+    Ok(())
+}
+
+fn dump(
+    heads: &Query<(Entity, &SnakeHead, &SnakeSegments)>,
+    positions: &Query<&Position>,
+) -> String {
+    let mut out = String::new();
+    for (head_entity, head, segments) in heads.iter() {
+        let _ = write!(out, "{head_entity} player {}:", head.player as usize + 1);
+        for ent in &segments.0 {
+            let _ = match positions.get(*ent) {
+                Ok(pos) => write!(out, " {ent}@({}, {})", pos.x, pos.y),
+                Err(_) => write!(out, " {ent}@missing"),
+            };
+        }
+        out.push('\n');
+    }
+    out
+}
@@ -0,0 +1,193 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{borrow::Cow, collections::HashMap, fmt::Display};
+
+use crate::{hot_reload::data_file, only_menu, progress::Progress, Menu};
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    Spanish,
+}
+
+impl Language {
+    pub const ALL: [Self; 2] = [Self::English, Self::Spanish];
+
+    // Always shown in the language itself so it can be found from any other.
+    pub fn native_name(self) -> &'static str {
+        match self {
+            Self::English => "English",
+            Self::Spanish => "Español",
+        }
+    }
+
+    fn table(self) -> Cow<'static, str> {
+        match self {
+            Self::English => data_file!("locales/en.ron"),
+            Self::Spanish => data_file!("locales/es.ron"),
+        }
+    }
+
+    fn strings(self) -> HashMap<String, String> {
+        ron::from_str(&self.table()).unwrap_or_else(|err| {
+            warn!("Broken {} string table: {err}", self.native_name());
+            HashMap::new()
+        })
+    }
+}
+
+// Every piece of text shown to the player is looked up here by key.
+// Keys missing from a translation fall back to English.
+#[derive(Resource)]
+pub struct Locale {
+    language: Language,
+    strings: HashMap<String, String>,
+    fallback: HashMap<String, String>,
+}
+
+impl Locale {
+    fn new(language: Language) -> Self {
+        Self {
+            language,
+            strings: language.strings(),
+            fallback: Language::English.strings(),
+        }
+    }
+
+    // Picks up edited string tables, for hot reloading.
+    pub fn reload(&mut self) {
+        *self = Self::new(self.language);
+    }
+
+    pub fn text<'a>(&'a self, key: &'a str) -> &'a str {
+        self.strings
+            .get(key)
+            .or_else(|| self.fallback.get(key))
+            .map_or(key, String::as_str)
+    }
+
+    // Fills in `{name}` placeholders in the text for `key`.
+    pub fn format(&self, key: &str, args: &[(&str, &dyn Display)]) -> String {
+        let mut text = self.text(key).to_string();
+        for (name, value) in args {
+            text = text.replace(&format!("{{{name}}}"), &value.to_string());
+        }
+        text
+    }
+}
+
+#[derive(Component)]
+struct LanguagePicker;
+
+pub struct LocalePlugin;
+
+impl Plugin for LocalePlugin {
+    fn build(&self, app: &mut App) {
+        let language = app.world().resource::<Progress>().language;
+        app.insert_resource(Locale::new(language)).add_systems(
+            Update,
+            (
+                toggle_language_picker,
+                select_language,
+                apply_language,
+                update_language_picker,
+            )
+                .chain(),
+        );
+    }
+}
+
+fn toggle_language_picker(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    picker: Query<Entity, With<LanguagePicker>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::KeyL) {
+        return;
+    }
+
+    match picker.get_single() {
+        Ok(ent) => commands.entity(ent).despawn(),
+        Err(_) => {
+            commands.spawn((
+                Text::default(),
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(5.0),
+                    left: Val::Px(5.0),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+                LanguagePicker,
+                Menu,
+            ));
+        }
+    }
+}
+
+fn select_language(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut progress: ResMut<Progress>,
+    picker: Query<Entity, With<LanguagePicker>>,
+    menus: Query<Entity, With<Menu>>,
+) {
+    if !picker.get_single().is_ok_and(|ent| only_menu(ent, &menus)) {
+        return;
+    }
+
+    const KEYS: [KeyCode; 2] = [KeyCode::Digit1, KeyCode::Digit2];
+    for (key, language) in KEYS.iter().zip(Language::ALL) {
+        if keyboard_input.just_pressed(*key) && progress.language != language {
+            progress.language = language;
+            progress.save();
+        }
+    }
+}
+
+// The language is a per-profile setting, so this also follows profile switches.
+fn apply_language(progress: Res<Progress>, mut locale: ResMut<Locale>) {
+    if progress.is_changed() && progress.language != locale.language {
+        *locale = Locale::new(progress.language);
+    }
+}
+
+fn update_language_picker(locale: Res<Locale>, mut picker: Query<&mut Text, With<LanguagePicker>>) {
+    for mut text in picker.iter_mut() {
+        let mut lines = vec![locale.text("language-title").to_string()];
+        for (i, language) in Language::ALL.iter().enumerate() {
+            let marker = if *language == locale.language {
+                " *"
+            } else {
+                ""
+            };
+            lines.push(format!("{}. {}{marker}", i + 1, language.native_name()));
+        }
+        text.0 = lines.join("\n");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn every_table_parses_with_the_english_keys() {
+        let parse = |language: Language| -> BTreeSet<String> {
+            let strings: HashMap<String, String> = ron::from_str(&language.table())
+                .unwrap_or_else(|err| panic!("{} table: {err}", language.native_name()));
+            strings.into_keys().collect()
+        };
+        let english = parse(Language::English);
+        for language in Language::ALL {
+            let keys = parse(language);
+            assert_eq!(
+                keys.symmetric_difference(&english).collect::<Vec<_>>(),
+                Vec::<&String>::new(),
+                "{} keys differ from English",
+                language.native_name()
+            );
+        }
+    }
+}
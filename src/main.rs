@@ -1,15 +1,92 @@
-use bevy::prelude::*;
-use rand::prelude::random;
+use bevy::{prelude::*, window::PrimaryWindow};
+use locale::Locale;
+use pickups::Pickups;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use settings::ArenaOrientation;
 use std::time::Duration;
 
+mod accessibility;
+mod adaptive;
+mod arcade;
+mod background_pause;
+mod brains;
+mod cloud_sync;
+mod coach;
+mod codex;
+mod controls;
+mod crash_report;
+mod credits;
+mod dash;
+mod death_camera;
+mod event_feed;
+mod event_log;
+mod food_placement;
+mod gamepad;
+mod hot_reload;
+mod input_latency;
+mod invariants;
+mod locale;
+mod missions;
+mod modifiers;
+mod mods;
+mod mutations;
+mod objectives;
+mod obstacles;
+mod onboarding;
+mod pickups;
+mod planner;
+mod profiles;
+mod progress;
+mod saved_run;
+mod score_breakdown;
+mod seasons;
+mod second_window;
+mod settings;
+mod shop;
+mod sim_speed;
+mod spectate;
+mod tail_camera;
+mod telemetry;
+mod time_travel;
+mod tips;
+mod victory;
+mod vignette;
+mod window_chrome;
+mod world_events;
+
 const ARENA_WIDTH: u32 = 10;
 const ARENA_HEIGHT: u32 = 10;
 
 const SNAKE_HEAD_COLOR: Color = Color::srgb(0.7, 0.7, 0.7);
 const SNAKE_SEGMENT_COLOR: Color = Color::srgb(0.3, 0.3, 0.3);
-const FOOD_COLOR: Color = Color::srgb(1.0, 0.0, 1.0);
+const PLAYER_TWO_HEAD_COLOR: Color = Color::srgb(0.4, 0.7, 0.9);
+const PLAYER_TWO_SEGMENT_COLOR: Color = Color::srgb(0.15, 0.35, 0.5);
+const PROJECTILE_COLOR: Color = Color::srgb(1.0, 0.6, 0.1);
+const FROZEN_HEAD_COLOR: Color = Color::srgb(0.8, 0.95, 1.0);
+const FROZEN_SEGMENT_COLOR: Color = Color::srgb(0.45, 0.7, 0.85);
+const CONFUSED_HEAD_COLOR: Color = Color::srgb(0.85, 1.0, 0.5);
+const CONFUSED_SEGMENT_COLOR: Color = Color::srgb(0.45, 0.6, 0.2);
+const SHIELD_COLOR: Color = Color::srgb(1.0, 0.85, 0.2);
+
+const COOP_LIVES: u32 = 3;
+const COOP_SCORE_GOAL: u32 = 20;
+const ARCADE_LIVES: u32 = 3;
 
-#[derive(PartialEq, Clone, Copy)]
+const PROJECTILE_COOLDOWN_TICKS: u32 = 8;
+const PROJECTILE_TICKS_PER_CELL: u32 = 2;
+const PROJECTILE_CUT: usize = 3;
+// how much body segments swell and how fast the swell runs down the snake
+const WIGGLE_AMPLITUDE: f32 = 0.08;
+const WIGGLE_SPEED: f32 = 6.0;
+const WIGGLE_PHASE_PER_SEGMENT: f32 = 0.6;
+const AFTERIMAGE_SECS: f32 = 0.25;
+const AFTERIMAGE_ALPHA: f32 = 0.35;
+// how much fainter and shorter lived afterimages are with reduced motion
+const AFTERIMAGE_REDUCED: f32 = 0.5;
+
+#[derive(PartialEq, Clone, Copy, Serialize, Deserialize)]
 enum Direction {
     Left,
     Up,
@@ -28,12 +105,108 @@ impl Direction {
     }
 }
 
-#[derive(Component, Clone, Copy, PartialEq, Eq)]
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+enum GameMode {
+    Classic,
+    Coop,
+    Tron,
+    Versus,
+    Arcade,
+    Objective,
+}
+
+impl GameMode {
+    fn from_args() -> Self {
+        match arg_value("--mode").as_deref() {
+            Some("coop") => Self::Coop,
+            Some("tron") => Self::Tron,
+            Some("versus") => Self::Versus,
+            Some("arcade") => Self::Arcade,
+            Some("objective") => Self::Objective,
+            _ => Self::Classic,
+        }
+    }
+
+    fn players(self) -> &'static [Player] {
+        match self {
+            Self::Classic | Self::Arcade | Self::Objective => &[Player::One],
+            Self::Coop | Self::Tron | Self::Versus => &[Player::One, Player::Two],
+        }
+    }
+
+    fn lives(self) -> u32 {
+        match self {
+            Self::Classic | Self::Tron | Self::Versus | Self::Objective => 0,
+            Self::Coop => COOP_LIVES,
+            Self::Arcade => ARCADE_LIVES,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+enum Player {
+    One,
+    Two,
+}
+
+impl Player {
+    fn start_position(self) -> Position {
+        match self {
+            Self::One => Position { x: 3, y: 3 },
+            Self::Two => Position { x: 6, y: 3 },
+        }
+    }
+
+    fn name(self, locale: &Locale) -> &str {
+        locale.text(match self {
+            Self::One => "player-one",
+            Self::Two => "player-two",
+        })
+    }
+
+    fn colors(self) -> (Color, Color) {
+        match self {
+            Self::One => (SNAKE_HEAD_COLOR, SNAKE_SEGMENT_COLOR),
+            Self::Two => (PLAYER_TWO_HEAD_COLOR, PLAYER_TWO_SEGMENT_COLOR),
+        }
+    }
+}
+
+#[derive(Component, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 struct Position {
     x: i32,
     y: i32,
 }
 
+impl Position {
+    fn moved(self, direction: Direction) -> Self {
+        let (dx, dy) = match direction {
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+            Direction::Up => (0, 1),
+            Direction::Down => (0, -1),
+        };
+        Self {
+            x: self.x + dx,
+            y: self.y + dy,
+        }
+    }
+
+    fn random(rng: &mut GameRng) -> Self {
+        Self {
+            x: rng.0.gen_range(0..ARENA_WIDTH as i32),
+            y: rng.0.gen_range(0..ARENA_HEIGHT as i32),
+        }
+    }
+
+    fn in_arena(self) -> bool {
+        self.x >= 0
+            && self.y >= 0
+            && (self.x as u32) < ARENA_WIDTH
+            && (self.y as u32) < ARENA_HEIGHT
+    }
+}
+
 #[derive(Component)]
 struct Size {
     width: f32,
@@ -52,33 +225,236 @@ impl Size {
 #[derive(Component)]
 struct SnakeHead {
     direction: Direction,
+    player: Player,
 }
 
 #[derive(Component)]
 struct SnakeSegment;
 
-#[derive(Resource, Default)]
+// Lives on the head entity; the first entry is the head itself.
+#[derive(Component, Default)]
 struct SnakeSegments(Vec<Entity>);
 
-#[derive(Resource, Default)]
+#[derive(Component, Default)]
 struct LastTailPosition(Option<Position>);
 
+// Cells the head moved out of on its last move, one for each step of a dash.
+#[derive(Component, Default)]
+struct HeadPath(Vec<Position>);
+
 #[derive(Component)]
 struct Food;
 
+#[derive(Component, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+enum PowerUp {
+    Projectile,
+    Freeze,
+    Confusion,
+    Shield,
+    // charges the dash meter
+    Energy,
+}
+
+impl PowerUp {
+    fn name(self, locale: &Locale) -> &str {
+        locale.text(match self {
+            Self::Projectile => "power-up-projectile",
+            Self::Freeze => "power-up-freeze",
+            Self::Confusion => "power-up-confusion",
+            Self::Shield => "power-up-shield",
+            Self::Energy => "power-up-energy",
+        })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+enum StatusEffect {
+    Frozen,
+    Confused,
+}
+
+impl StatusEffect {
+    fn label(self, locale: &Locale) -> &str {
+        locale.text(match self {
+            Self::Frozen => "effect-frozen",
+            Self::Confused => "effect-confused",
+        })
+    }
+}
+
+// Timed effects on a snake, counted down once per movement tick.
+// Applying an effect that is already active adds to its remaining ticks.
+#[derive(Component, Default)]
+struct StatusEffects(Vec<(StatusEffect, u32)>);
+
+impl StatusEffects {
+    fn apply(&mut self, effect: StatusEffect, ticks: u32) {
+        match self.0.iter_mut().find(|(active, _)| *active == effect) {
+            Some((_, remaining)) => *remaining += ticks,
+            None => self.0.push((effect, ticks)),
+        }
+    }
+
+    fn has(&self, effect: StatusEffect) -> bool {
+        self.0.iter().any(|(active, _)| *active == effect)
+    }
+
+    // Modifier stage every direction a player presses goes through.
+    fn modify_input(&self, direction: Direction) -> Direction {
+        if self.has(StatusEffect::Confused) {
+            direction.opposite()
+        } else {
+            direction
+        }
+    }
+
+    fn tick(&mut self) {
+        for (_, remaining) in self.0.iter_mut() {
+            *remaining = remaining.saturating_sub(1);
+        }
+        self.0.retain(|(_, remaining)| *remaining > 0);
+    }
+}
+
+// Shots picked up from projectile power-ups, lives on the head entity.
+#[derive(Component, Default)]
+struct Launcher {
+    shots: u32,
+    cooldown: u32,
+}
+
+// Absorbs the next fatal collision, checked wherever a SnakeDiedEvent is handled.
+#[derive(Component)]
+struct Shield;
+
+#[derive(Component)]
+struct Pop(Timer);
+
+// A fading copy of the head left in a cell it just moved out of.
+#[derive(Component)]
+struct Afterimage {
+    fade: Timer,
+    alpha: f32,
+}
+
+#[derive(Component)]
+struct Projectile {
+    direction: Direction,
+    owner: Entity,
+    ticks: u32,
+}
+
+// Cell a light cycle has already passed through in tron mode.
+#[derive(Component)]
+struct Trail(Player);
+
+// Everything besides the snakes that is cleared when a run resets.
+type ArenaItems = Or<(
+    With<Food>,
+    With<PowerUp>,
+    With<Projectile>,
+    With<Trail>,
+    With<obstacles::Block>,
+)>;
+
+// Everything the versus HUD lists for each snake.
+type HudSnake = (
+    &'static SnakeHead,
+    &'static SnakeSegments,
+    &'static Launcher,
+    &'static dash::Dash,
+    &'static StatusEffects,
+    Has<Shield>,
+);
+
+// Cells new food can't land on.
+type Occupied = Or<(With<SnakeSegment>, With<Food>, With<obstacles::Block>)>;
+
+// All gameplay randomness comes from here so a saved run resumes exactly.
+#[derive(Resource)]
+struct GameRng(ChaCha8Rng);
+
 #[derive(Resource)]
 struct FixedTimer(Timer);
 
 #[derive(Resource)]
 struct FoodSpawnerTimer(Timer);
 
+#[derive(Resource)]
+struct PowerUpSpawnerTimer(Timer);
+
+#[derive(Resource, Default)]
+struct Score(u32);
+
+// Respawns left for the whole team; a death with none left ends the run.
+#[derive(Resource)]
+struct Lives(u32);
+
+#[derive(Component)]
+pub struct Hud;
+
+// Any open panel or dialog the player is interacting with.
+#[derive(Component)]
+struct Menu;
+
+// Panels share the number keys, so each only takes them while it's the one
+// menu open, rather than a press landing in several at once.
+fn only_menu(panel: Entity, menus: &Query<Entity, With<Menu>>) -> bool {
+    menus.iter().all(|ent| ent == panel)
+}
+
+#[derive(Component)]
+struct Toast(Timer);
+
+#[derive(Event)]
+struct GrowthEvent(Entity);
+
 #[derive(Event)]
-struct GrowthEvent;
+struct SnakeDiedEvent(Entity);
+
+#[derive(Event)]
+struct PowerUpPickedEvent(Entity, PowerUp);
+
+// A player tried to turn straight back on themselves.
+#[derive(Event)]
+struct ReversalAttemptEvent(Entity);
+
+// A snake lost part of its body to another snake or a projectile.
+#[derive(Event)]
+struct SeveredEvent(Entity);
 
 #[derive(Event)]
 struct GameOverEvent;
 
+// Where a player's points came from, for the breakdown at the end of a run.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+enum ScoreSource {
+    Food,
+    // eating objective pellets in order
+    OrderBonus,
+    // the extra from arcade mutations that multiply the score
+    MutationBonus,
+    // the extra from a seasonal event's special food
+    SeasonalBonus,
+}
+
+// Points scored, added to the score by `tally_score`.
+#[derive(Event)]
+struct ScoreEvent {
+    source: ScoreSource,
+    points: u32,
+}
+
+// Value following `name` on the command line, e.g. `--mode coop`.
+fn arg_value(name: &str) -> Option<String> {
+    let mut args = std::env::args();
+    args.find(|arg| arg == name)?;
+    args.next()
+}
+
 fn main() {
+    let mode = GameMode::from_args();
+
     App::new()
         .add_plugins(DefaultPlugins.set(WindowPlugin {
             primary_window: Some(Window {
@@ -86,6 +462,8 @@ fn main() {
                 resolution: (500.0, 500.0).into(),
                 ..default()
             }),
+            // closing goes through the quit dialog so a run isn't lost by accident
+            close_when_requested: false,
             ..default()
         }))
         .insert_resource(ClearColor(Color::srgb(0.04, 0.04, 0.04)))
@@ -97,245 +475,1099 @@ fn main() {
             Duration::from_secs(1),
             TimerMode::Repeating,
         )))
-        .insert_resource(SnakeSegments::default())
-        .insert_resource(LastTailPosition::default())
-        .add_systems(Startup, (setup_camera, spawn_snake))
+        .insert_resource(PowerUpSpawnerTimer(Timer::new(
+            Duration::from_secs(6),
+            TimerMode::Repeating,
+        )))
+        .insert_resource(GameRng(ChaCha8Rng::from_entropy()))
+        .insert_resource(mode)
+        .insert_resource(profiles::Profile::from_args())
+        .insert_resource(Score::default())
+        .insert_resource(Lives(mode.lives()))
+        .add_systems(Startup, (setup_camera, setup_hud, spawn_snakes))
         .add_systems(
             Update,
             (
                 (
                     snake_movement_input,
+                    projectile_input,
                     snake_movement,
+                    tick_status_effects,
+                    projectile_movement,
+                    trail_collision,
+                    body_cutting,
+                    projectile_hits,
+                    snake_death,
                     game_over,
                     snake_eating,
                     snake_growth,
+                    score_breakdown::tally_score,
+                    power_up_pickup,
+                    coop_goal,
+                    tron_last_alive,
                 )
-                    .chain(),
-                food_spawner,
+                    .chain()
+                    .run_if(death_camera::idle),
+                food_spawner.run_if(random_food),
+                power_up_spawner,
+                animate_projectiles,
+                status_effect_visuals,
+                animate_pops,
+                spawn_afterimages.after(snake_movement),
+                fade_afterimages,
+                update_hud,
+                expire_toasts,
             ),
         )
-        .add_systems(PostUpdate, (position_translation, size_scaling))
+        .add_systems(
+            PostUpdate,
+            (
+                position_translation,
+                size_scaling,
+                wiggle_segments.after(size_scaling),
+            ),
+        )
+        .add_plugins((
+            (
+                pickups::PickupsPlugin,
+                food_placement::FoodPlacementPlugin,
+                world_events::WorldEventsPlugin,
+                seasons::SeasonsPlugin,
+                obstacles::ObstaclesPlugin,
+            ),
+            profiles::ProfilesPlugin,
+            progress::ProgressPlugin,
+            locale::LocalePlugin,
+            accessibility::AccessibilityPlugin,
+            settings::SettingsPlugin,
+            controls::ControlsPlugin,
+            gamepad::GamepadPlugin,
+            credits::CreditsPlugin,
+            background_pause::BackgroundPausePlugin,
+            (
+                window_chrome::WindowChromePlugin,
+                vignette::VignettePlugin,
+                death_camera::DeathCameraPlugin,
+                tail_camera::TailCameraPlugin,
+                event_feed::EventFeedPlugin,
+            ),
+            (
+                shop::ShopPlugin,
+                missions::MissionsPlugin,
+                victory::VictoryPlugin,
+                arcade::ArcadePlugin,
+                modifiers::ModifiersPlugin,
+                adaptive::AdaptivePlugin,
+                tips::TipsPlugin,
+                mutations::MutationsPlugin,
+                objectives::ObjectivesPlugin,
+                coach::CoachPlugin,
+                codex::CodexPlugin,
+                onboarding::OnboardingPlugin,
+                score_breakdown::ScoreBreakdownPlugin,
+                dash::DashPlugin,
+            ),
+            (
+                cloud_sync::CloudSyncPlugin,
+                telemetry::TelemetryPlugin,
+                spectate::SpectatePlugin,
+                input_latency::InputLatencyPlugin,
+                second_window::SecondWindowPlugin,
+                brains::BrainsPlugin,
+            ),
+            (
+                mods::ModsPlugin,
+                hot_reload::HotReloadPlugin,
+                event_log::EventLogPlugin,
+            ),
+            (
+                saved_run::SavedRunPlugin,
+                crash_report::CrashReportPlugin,
+                time_travel::TimeTravelPlugin,
+                invariants::InvariantsPlugin,
+                sim_speed::SimSpeedPlugin,
+            ),
+        ))
         .add_event::<GrowthEvent>()
+        .add_event::<SnakeDiedEvent>()
+        .add_event::<PowerUpPickedEvent>()
+        .add_event::<ReversalAttemptEvent>()
+        .add_event::<SeveredEvent>()
         .add_event::<GameOverEvent>()
+        .add_event::<ScoreEvent>()
         .run();
 }
 
 fn setup_camera(mut commands: Commands) {
-    commands.spawn(Camera2d);
+    // the UI stays on the main camera when the tail inset is showing
+    commands.spawn((Camera2d, IsDefaultUiCamera));
+}
+
+fn setup_hud(mut commands: Commands) {
+    commands.spawn((
+        Text::default(),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(5.0),
+            left: Val::Px(5.0),
+            ..default()
+        },
+        Hud,
+    ));
+}
+
+fn update_hud(
+    locale: Res<Locale>,
+    mode: Res<GameMode>,
+    score: Res<Score>,
+    lives: Res<Lives>,
+    stage: Res<arcade::Stage>,
+    snakes: Query<HudSnake>,
+    mut hud: Single<&mut Text, With<Hud>>,
+) {
+    hud.0 = match *mode {
+        GameMode::Classic | GameMode::Objective => {
+            locale.format("hud-score", &[("score", &score.0)])
+        }
+        GameMode::Coop => locale.format(
+            "hud-coop",
+            &[
+                ("score", &score.0),
+                ("goal", &COOP_SCORE_GOAL),
+                ("lives", &lives.0),
+            ],
+        ),
+        GameMode::Arcade => locale.format(
+            "hud-arcade",
+            &[
+                ("stage", &stage.number),
+                ("eaten", &stage.eaten),
+                ("goal", &stage.food_goal()),
+                ("score", &score.0),
+                ("lives", &lives.0),
+            ],
+        ),
+        GameMode::Tron => locale.text("hud-tron").to_string(),
+        GameMode::Versus => {
+            let mut lines: Vec<(Player, String)> = snakes
+                .iter()
+                .map(|(head, segments, launcher, dash, effects, shielded)| {
+                    let mut line = locale.format(
+                        "hud-length",
+                        &[
+                            ("player", &head.player.name(&locale)),
+                            ("length", &segments.0.len()),
+                        ],
+                    );
+                    if launcher.shots > 0 {
+                        line += &locale.format("hud-shots", &[("shots", &launcher.shots)]);
+                        if launcher.cooldown > 0 {
+                            line +=
+                                &locale.format("hud-cooldown", &[("ticks", &launcher.cooldown)]);
+                        }
+                    }
+                    if dash.ready() {
+                        line += locale.text("hud-dash-ready");
+                    } else if dash.charge > 0 {
+                        line += &locale.format(
+                            "hud-dash",
+                            &[("charge", &dash.charge), ("full", &dash::DASH_CHARGE)],
+                        );
+                    }
+                    if shielded {
+                        line += locale.text("hud-shield");
+                    }
+                    for (effect, _) in effects.0.iter() {
+                        line += &format!(" {}", effect.label(&locale));
+                    }
+                    (head.player, line)
+                })
+                .collect();
+            lines.sort();
+            lines
+                .into_iter()
+                .map(|(_, line)| line)
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+    };
+}
+
+fn show_toast(commands: &mut Commands, message: impl Into<String>) -> Entity {
+    commands
+        .spawn((
+            Text::new(message),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Percent(45.0),
+                width: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            TextLayout::new_with_justify(JustifyText::Center),
+            Toast(Timer::from_seconds(2.0, TimerMode::Once)),
+        ))
+        .id()
+}
+
+fn expire_toasts(time: Res<Time>, mut commands: Commands, mut toasts: Query<(Entity, &mut Toast)>) {
+    for (ent, mut toast) in toasts.iter_mut() {
+        if toast.0.tick(time.delta()).finished() {
+            commands.entity(ent).despawn();
+        }
+    }
 }
 
-fn size_scaling(window: Single<&Window>, mut q: Query<(&Size, &mut Transform)>) {
+fn size_scaling(
+    window: Single<&Window, With<PrimaryWindow>>,
+    mut q: Query<(&Size, &mut Transform)>,
+) {
     for (sprite_size, mut transform) in q.iter_mut() {
         transform.scale = Vec3::new(
-            sprite_size.width / ARENA_WIDTH as f32 * window.width() as f32,
-            sprite_size.height / ARENA_HEIGHT as f32 * window.height() as f32,
+            sprite_size.width / ARENA_WIDTH as f32 * window.width(),
+            sprite_size.height / ARENA_HEIGHT as f32 * window.height(),
             1.0,
         )
     }
 }
 
-fn position_translation(window: Single<&Window>, mut q: Query<(&Position, &mut Transform)>) {
+// Centre of a cell in world space.
+fn cell_center(pos: Position, orientation: ArenaOrientation, window: &Window) -> Vec2 {
     fn convert(pos: f32, bound_window: f32, bound_game: f32) -> f32 {
         let tile_size = bound_window / bound_game;
         pos / bound_game * bound_window - (bound_window / 2.) + (tile_size / 2.)
     }
 
+    let pos = orientation.place(pos);
+    Vec2::new(
+        convert(pos.x as f32, window.width(), ARENA_WIDTH as f32),
+        convert(pos.y as f32, window.height(), ARENA_HEIGHT as f32),
+    )
+}
+
+fn position_translation(
+    progress: Res<progress::Progress>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    mut q: Query<(&Position, &mut Transform)>,
+) {
+    // z is left alone so things can be layered under the grid
     for (pos, mut transform) in q.iter_mut() {
-        transform.translation = Vec3::new(
-            convert(pos.x as f32, window.width() as f32, ARENA_WIDTH as f32),
-            convert(pos.y as f32, window.height() as f32, ARENA_HEIGHT as f32),
-            0.0,
-        )
+        transform.translation =
+            cell_center(*pos, progress.orientation, &window).extend(transform.translation.z);
     }
 }
 
-fn spawn_snake(mut commands: Commands, mut segments: ResMut<SnakeSegments>) {
-    *segments = SnakeSegments(vec![
-        commands
-            .spawn((
-                Sprite {
-                    color: SNAKE_HEAD_COLOR,
-                    ..default()
-                },
-                Transform {
-                    scale: Vec3::new(10.0, 10.0, 10.0),
-                    ..default()
-                },
-            ))
-            .insert((
-                SnakeHead {
-                    direction: Direction::Up,
-                },
-                SnakeSegment,
-                Position { x: 3, y: 3 },
-                Size::square(0.8),
-            ))
-            .id(),
-        spawn_snake_segment(commands, Position { x: 3, y: 2 }),
-    ]);
+fn spawn_snakes(mut commands: Commands, mode: Res<GameMode>) {
+    for player in mode.players() {
+        spawn_snake(&mut commands, *player);
+    }
+}
+
+fn spawn_snake(commands: &mut Commands, player: Player) {
+    let start = player.start_position();
+    spawn_snake_at(
+        commands,
+        player,
+        Direction::Up,
+        &[
+            start,
+            Position {
+                x: start.x,
+                y: start.y - 1,
+            },
+        ],
+    );
+}
+
+// Spawns a snake whose head is at `positions[0]` and body follows the rest.
+fn spawn_snake_at(
+    commands: &mut Commands,
+    player: Player,
+    direction: Direction,
+    positions: &[Position],
+) -> Entity {
+    let (head_color, segment_color) = player.colors();
+    let head = commands
+        .spawn((
+            Sprite {
+                color: head_color,
+                ..default()
+            },
+            Transform {
+                scale: Vec3::new(10.0, 10.0, 10.0),
+                ..default()
+            },
+        ))
+        .insert((
+            SnakeHead { direction, player },
+            SnakeSegment,
+            positions[0],
+            Size::square(0.8),
+            Launcher::default(),
+            dash::Dash::default(),
+            StatusEffects::default(),
+        ))
+        .id();
+    let mut segments = vec![head];
+    for position in positions.iter().skip(1) {
+        segments.push(spawn_snake_segment(commands, segment_color, *position));
+    }
+    commands.entity(head).insert((
+        SnakeSegments(segments),
+        LastTailPosition::default(),
+        HeadPath::default(),
+    ));
+    head
 }
 
-fn spawn_snake_segment(mut commands: Commands, position: Position) -> Entity {
+fn spawn_snake_segment(commands: &mut Commands, color: Color, position: Position) -> Entity {
     commands
-        .spawn(Sprite {
-            color: SNAKE_SEGMENT_COLOR,
-            ..default()
-        })
+        .spawn(Sprite { color, ..default() })
         .insert((SnakeSegment, position, Size::square(0.65)))
         .id()
 }
 
 fn snake_movement_input(
     keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut heads: Query<&mut SnakeHead>,
+    progress: Res<progress::Progress>,
+    modifiers: Res<modifiers::Modifiers>,
+    gamepads: Query<(Entity, &Gamepad)>,
+    mut heads: Query<(Entity, &mut SnakeHead, &StatusEffects), Without<brains::Bot>>,
+    mut reversing: Local<Vec<Entity>>,
+    mut reversal_writer: EventWriter<ReversalAttemptEvent>,
 ) {
-    if let Some(mut head) = heads.iter_mut().next() {
-        let dir: Direction = if keyboard_input.pressed(KeyCode::ArrowLeft) {
-            Direction::Left
-        } else if keyboard_input.pressed(KeyCode::ArrowDown) {
-            Direction::Down
-        } else if keyboard_input.pressed(KeyCode::ArrowUp) {
-            Direction::Up
-        } else if keyboard_input.pressed(KeyCode::ArrowRight) {
-            Direction::Right
-        } else {
-            head.direction
-        };
+    for (head_entity, mut head, effects) in heads.iter_mut() {
+        let pressed = progress
+            .controls
+            .player(head.player)
+            .directions()
+            .iter()
+            .find(|(key, _)| keyboard_input.pressed(*key))
+            .map(|(_, dir)| *dir)
+            .or_else(|| {
+                let (_, gamepad) = gamepad::player_gamepad(head.player, &gamepads)?;
+                gamepad::DPAD
+                    .iter()
+                    .find(|(button, _)| gamepad.pressed(*button))
+                    .map(|(_, dir)| *dir)
+            });
+        let dir = pressed.map_or(head.direction, |dir| {
+            effects.modify_input(progress.orientation.steer(modifiers.steer(dir)))
+        });
         if dir != head.direction.opposite() {
             head.direction = dir;
+            reversing.retain(|ent| *ent != head_entity);
+        } else if !reversing.contains(&head_entity) {
+            // once per press, not every frame it's held
+            reversing.push(head_entity);
+            reversal_writer.send(ReversalAttemptEvent(head_entity));
+        }
+    }
+}
+
+fn projectile_input(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    progress: Res<progress::Progress>,
+    gamepads: Query<(Entity, &Gamepad)>,
+    mut heads: Query<(Entity, &SnakeHead, &Position, &mut Launcher)>,
+) {
+    for (head_entity, head, head_pos, mut launcher) in heads.iter_mut() {
+        let fired = keyboard_input.just_pressed(progress.controls.player(head.player).fire())
+            || gamepad::player_gamepad(head.player, &gamepads)
+                .is_some_and(|(_, gamepad)| gamepad.just_pressed(gamepad::FIRE_BUTTON));
+        if !fired || launcher.shots == 0 || launcher.cooldown > 0 {
+            continue;
         }
+        launcher.shots -= 1;
+        launcher.cooldown = PROJECTILE_COOLDOWN_TICKS;
+        spawn_projectile(
+            &mut commands,
+            Projectile {
+                direction: head.direction,
+                owner: head_entity,
+                ticks: 0,
+            },
+            head_pos.moved(head.direction),
+        );
     }
 }
 
+fn spawn_projectile(commands: &mut Commands, projectile: Projectile, position: Position) {
+    commands.spawn((
+        Sprite {
+            color: PROJECTILE_COLOR,
+            ..default()
+        },
+        projectile,
+        position,
+        Size::square(0.4),
+    ));
+}
+
 fn snake_movement(
+    mut commands: Commands,
+    mode: Res<GameMode>,
     time: Res<Time>,
     mut timer: ResMut<FixedTimer>,
-    segments: ResMut<SnakeSegments>,
-    mut heads: Query<(Entity, &SnakeHead)>,
+    mut heads: Query<(
+        Entity,
+        &SnakeHead,
+        &SnakeSegments,
+        &mut LastTailPosition,
+        &mut HeadPath,
+        &StatusEffects,
+        &mut dash::Dash,
+    )>,
     mut positions: Query<&mut Position>,
-    mut last_tail_position: ResMut<LastTailPosition>,
-    mut game_over_writer: EventWriter<GameOverEvent>,
+    mut died_writer: EventWriter<SnakeDiedEvent>,
 ) {
     if !timer.0.tick(time.delta()).just_finished() {
         return;
     }
 
-    if let Some((head_entity, head)) = heads.iter_mut().next() {
-        // get position for every snake segment
-        let segment_positions: Vec<Position> = segments
-            .0
-            .iter()
-            .map(|e| *positions.get_mut(*e).unwrap())
-            .collect();
+    for (head_entity, head, segments, mut last_tail_position, mut head_path, effects, mut dash) in
+        heads.iter_mut()
+    {
+        if effects.has(StatusEffect::Frozen) {
+            continue;
+        }
 
-        // get position for the snake's head
-        let mut head_pos = positions.get_mut(head_entity).unwrap();
+        // a dash covers several cells in one tick, with the body following
+        // along, and only the cell it lands on is checked
+        let steps = dash.steps(*positions.get(head_entity).unwrap(), head.direction);
+        let mut segment_positions = Vec::new();
+        head_path.0.clear();
+        for _ in 0..steps {
+            // get position for every snake segment
+            segment_positions = segments
+                .0
+                .iter()
+                .map(|e| *positions.get(*e).unwrap())
+                .collect();
+            head_path.0.push(segment_positions[0]);
 
-        // proceed with moving the snake's head
-        match &head.direction {
-            Direction::Left => {
-                head_pos.x -= 1;
+            // light cycles leave a permanent trail behind the head
+            if *mode == GameMode::Tron {
+                spawn_trail(&mut commands, head.player, segment_positions[0]);
             }
-            Direction::Right => {
-                head_pos.x += 1;
-            }
-            Direction::Up => {
-                head_pos.y += 1;
-            }
-            Direction::Down => {
-                head_pos.y -= 1;
-            }
-        };
+
+            // proceed with moving the snake's head
+            let mut head_pos = positions.get_mut(head_entity).unwrap();
+            *head_pos = head_pos.moved(head.direction);
+
+            // segment_position = n, segment = n + 1
+            // I.e. for each segment position, we have access to the next segment
+            // set the position of the next segment to the current position
+            segment_positions
+                .iter()
+                .zip(segments.0.iter().skip(1))
+                .for_each(|(pos, segment)| {
+                    *positions.get_mut(*segment).unwrap() = *pos;
+                });
+        }
+
+        // get position for the snake's head
+        let head_pos = *positions.get(head_entity).unwrap();
 
         // check that the head hsn't exceeded bounds of window/arena
-        if head_pos.x < 0
-            || head_pos.y < 0
-            || head_pos.x as u32 >= ARENA_WIDTH
-            || head_pos.y as u32 >= ARENA_HEIGHT
-        {
-            game_over_writer.send(GameOverEvent);
+        if !head_pos.in_arena() {
+            died_writer.send(SnakeDiedEvent(head_entity));
         }
 
-        // check that the head hasn't collided with segment positions
+        // check that the head hasn't collided with its own segments,
+        // teammates pass through each other
         if segment_positions.contains(&head_pos) {
-            game_over_writer.send(GameOverEvent);
+            died_writer.send(SnakeDiedEvent(head_entity));
         }
 
-        // segment_position = n, segment = n + 1
-        // I.e. for each segment position, we have access to the next segment
-        // set the position of the next segment to the current position
-        segment_positions
-            .iter()
-            .zip(segments.0.iter().skip(1))
-            .for_each(|(pos, segment)| {
-                *positions.get_mut(*segment).unwrap() = *pos;
-            });
-
         // update last tail position with last segment position
         *last_tail_position = LastTailPosition(Some(*segment_positions.last().unwrap()));
     }
 }
 
-fn food_spawner(time: Res<Time>, mut timer: ResMut<FoodSpawnerTimer>, mut commands: Commands) {
-    if !timer.0.tick(time.delta()).just_finished() {
+fn spawn_trail(commands: &mut Commands, player: Player, position: Position) {
+    let (_, trail_color) = player.colors();
+    commands.spawn((
+        Sprite {
+            color: trail_color,
+            ..default()
+        },
+        Trail(player),
+        position,
+        Size::square(0.9),
+    ));
+}
+
+fn tick_status_effects(timer: Res<FixedTimer>, mut q: Query<&mut StatusEffects>) {
+    if !timer.0.just_finished() {
         return;
     }
 
-    commands
-        .spawn(Sprite {
-            color: FOOD_COLOR,
+    for mut effects in q.iter_mut() {
+        effects.tick();
+    }
+}
+
+fn status_effect_visuals(
+    progress: Res<progress::Progress>,
+    heads: Query<(&SnakeHead, &SnakeSegments, &StatusEffects, Has<Shield>)>,
+    mut sprites: Query<&mut Sprite>,
+) {
+    for (head, segments, effects, shielded) in heads.iter() {
+        let (mut head_color, segment_color) = if effects.has(StatusEffect::Frozen) {
+            (FROZEN_HEAD_COLOR, FROZEN_SEGMENT_COLOR)
+        } else if effects.has(StatusEffect::Confused) {
+            (CONFUSED_HEAD_COLOR, CONFUSED_SEGMENT_COLOR)
+        } else if head.player == Player::One {
+            progress.selected.colors()
+        } else {
+            head.player.colors()
+        };
+        if shielded {
+            head_color = SHIELD_COLOR;
+        }
+        for (i, ent) in segments.0.iter().enumerate() {
+            if let Ok(mut sprite) = sprites.get_mut(*ent) {
+                sprite.color = if i == 0 { head_color } else { segment_color };
+            }
+        }
+    }
+}
+
+fn spawn_pop(commands: &mut Commands, position: Position) {
+    commands.spawn((
+        Sprite {
+            color: SHIELD_COLOR,
             ..default()
-        })
-        .insert((
-            Food,
-            Position {
-                x: (random::<f32>() * ARENA_WIDTH as f32) as i32,
-                y: (random::<f32>() * ARENA_HEIGHT as f32) as i32,
-            },
-            Size::square(0.8),
-        ));
+        },
+        Pop(Timer::from_seconds(0.3, TimerMode::Once)),
+        position,
+        Size::square(0.8),
+    ));
+}
+
+fn animate_pops(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut pops: Query<(Entity, &mut Pop, &mut Size, &mut Sprite)>,
+) {
+    for (ent, mut pop, mut size, mut sprite) in pops.iter_mut() {
+        if pop.0.tick(time.delta()).finished() {
+            commands.entity(ent).despawn();
+            continue;
+        }
+        let progress = pop.0.fraction();
+        *size = Size::square(0.8 + progress);
+        sprite.color = SHIELD_COLOR.with_alpha(1.0 - progress);
+    }
+}
+
+// A wave of swelling runs down the body so the snake looks alive. It is done
+// on the sprites' transforms rather than in a material so skins and status
+// effect colours keep working unchanged.
+fn wiggle_segments(
+    time: Res<Time>,
+    progress: Res<progress::Progress>,
+    heads: Query<&SnakeSegments>,
+    mut transforms: Query<&mut Transform, (With<SnakeSegment>, Without<SnakeHead>)>,
+) {
+    if progress.reduced_motion {
+        return;
+    }
+
+    let elapsed = time.elapsed_secs();
+    for segments in heads.iter() {
+        for (i, ent) in segments.0.iter().enumerate().skip(1) {
+            let Ok(mut transform) = transforms.get_mut(*ent) else {
+                continue;
+            };
+            let phase = elapsed * WIGGLE_SPEED - i as f32 * WIGGLE_PHASE_PER_SEGMENT;
+            let swell = 1.0 + WIGGLE_AMPLITUDE * phase.sin();
+            transform.scale.x *= swell;
+            transform.scale.y *= swell;
+        }
+    }
+}
+
+// Dashes leave afterimages in every cell the head passes over, ordinary
+// moves only with the always-on setting.
+fn spawn_afterimages(
+    mut commands: Commands,
+    progress: Res<progress::Progress>,
+    timer: Res<FixedTimer>,
+    heads: Query<(&HeadPath, &Sprite), Changed<HeadPath>>,
+) {
+    if !timer.0.just_finished() {
+        return;
+    }
+
+    let (alpha, secs) = if progress.reduced_motion {
+        (
+            AFTERIMAGE_ALPHA * AFTERIMAGE_REDUCED,
+            AFTERIMAGE_SECS * AFTERIMAGE_REDUCED,
+        )
+    } else {
+        (AFTERIMAGE_ALPHA, AFTERIMAGE_SECS)
+    };
+    for (head_path, sprite) in heads.iter() {
+        let dashed = head_path.0.len() > 1;
+        if !dashed && !progress.afterimages_always {
+            continue;
+        }
+        for position in head_path.0.iter() {
+            commands.spawn((
+                Sprite {
+                    color: sprite.color.with_alpha(alpha),
+                    ..default()
+                },
+                Afterimage {
+                    fade: Timer::from_seconds(secs, TimerMode::Once),
+                    alpha,
+                },
+                *position,
+                Size::square(0.65),
+            ));
+        }
+    }
+}
+
+fn fade_afterimages(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut afterimages: Query<(Entity, &mut Afterimage, &mut Sprite)>,
+) {
+    for (ent, mut afterimage, mut sprite) in afterimages.iter_mut() {
+        if afterimage.fade.tick(time.delta()).finished() {
+            commands.entity(ent).despawn();
+            continue;
+        }
+        let alpha = afterimage.alpha * (1.0 - afterimage.fade.fraction());
+        sprite.color.set_alpha(alpha);
+    }
+}
+
+fn projectile_movement(
+    mut commands: Commands,
+    timer: Res<FixedTimer>,
+    mut launchers: Query<&mut Launcher>,
+    mut projectiles: Query<(Entity, &mut Projectile, &mut Position)>,
+) {
+    // projectiles advance on the same tick as the snakes, checked after
+    // snake_movement has ticked the timer this frame
+    if !timer.0.just_finished() {
+        return;
+    }
+
+    for mut launcher in launchers.iter_mut() {
+        launcher.cooldown = launcher.cooldown.saturating_sub(1);
+    }
+
+    for (ent, mut projectile, mut pos) in projectiles.iter_mut() {
+        projectile.ticks += 1;
+        if projectile.ticks % PROJECTILE_TICKS_PER_CELL != 0 {
+            continue;
+        }
+        *pos = pos.moved(projectile.direction);
+        if !pos.in_arena() {
+            commands.entity(ent).despawn();
+        }
+    }
+}
+
+fn animate_projectiles(time: Res<Time>, mut q: Query<&mut Transform, With<Projectile>>) {
+    for mut transform in q.iter_mut() {
+        transform.rotate_z(time.delta_secs() * 8.0);
+    }
+}
+
+fn projectile_hits(
+    mut commands: Commands,
+    mut severed_writer: EventWriter<SeveredEvent>,
+    projectiles: Query<(Entity, &Projectile, &Position)>,
+    mut snakes: Query<(Entity, &mut SnakeSegments, &mut LastTailPosition)>,
+    positions: Query<&Position>,
+) {
+    for (ent, projectile, projectile_pos) in projectiles.iter() {
+        for (target, mut segments, mut last_tail_position) in snakes.iter_mut() {
+            if target == projectile.owner
+                || !segments
+                    .0
+                    .iter()
+                    .any(|segment| positions.get(*segment) == Ok(projectile_pos))
+            {
+                continue;
+            }
+            commands.entity(ent).despawn();
+            let cut = segments.0.len().saturating_sub(PROJECTILE_CUT).max(1);
+            sever_segments(
+                &mut commands,
+                &mut segments,
+                &mut last_tail_position,
+                &positions,
+                cut,
+            );
+            severed_writer.send(SeveredEvent(target));
+            break;
+        }
+    }
+}
+
+// Light cycles have no use for food and objectives lay out their own.
+fn random_food(mode: Res<GameMode>) -> bool {
+    !matches!(*mode, GameMode::Tron | GameMode::Objective)
+}
+
+// Every cell with nothing on it that food could be put on.
+fn free_cells(occupied: &Query<&Position, Occupied>) -> Vec<Position> {
+    let occupied: Vec<Position> = occupied.iter().copied().collect();
+    (0..ARENA_WIDTH as i32)
+        .flat_map(|x| (0..ARENA_HEIGHT as i32).map(move |y| Position { x, y }))
+        .filter(|pos| !occupied.contains(pos))
+        .collect()
+}
+
+fn food_spawner(
+    time: Res<Time>,
+    mut timer: ResMut<FoodSpawnerTimer>,
+    modifiers: Res<modifiers::Modifiers>,
+    placement: Res<food_placement::FoodPlacement>,
+    mut rng: ResMut<GameRng>,
+    mut commands: Commands,
+    occupied: Query<&Position, Occupied>,
+) {
+    if !timer.0.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    // only ever on an empty cell, a full board is won rather than fed
+    let mut free = free_cells(&occupied);
+    let count = if modifiers.double_food { 2 } else { 1 };
+    for _ in 0..count {
+        if free.is_empty() {
+            return;
+        }
+        let position = free.swap_remove(placement.pick(&free, &mut rng));
+        spawn_food(&mut commands, position);
+    }
+}
+
+fn power_up_spawner(
+    time: Res<Time>,
+    mode: Res<GameMode>,
+    mut timer: ResMut<PowerUpSpawnerTimer>,
+    mut rng: ResMut<GameRng>,
+    pickups: Res<Pickups>,
+    mut commands: Commands,
+) {
+    if !timer.0.tick(time.delta()).just_finished() || *mode != GameMode::Versus {
+        return;
+    }
+
+    let Some(power_up) = pickups.random_power_up(&mut rng) else {
+        return;
+    };
+    let position = Position::random(&mut rng);
+    commands.spawn((power_up, position));
+}
+
+fn power_up_pickup(
+    mut commands: Commands,
+    mut picked_writer: EventWriter<PowerUpPickedEvent>,
+    pickups: Res<Pickups>,
+    power_ups: Query<(Entity, &PowerUp, &Position)>,
+    mut heads: Query<
+        (
+            Entity,
+            &Position,
+            &mut Launcher,
+            &mut dash::Dash,
+            &mut StatusEffects,
+        ),
+        With<SnakeHead>,
+    >,
+) {
+    for (ent, power_up, power_up_pos) in power_ups.iter() {
+        let Some(picker) = heads
+            .iter()
+            .find(|(_, pos, ..)| *pos == power_up_pos)
+            .map(|(head_entity, ..)| head_entity)
+        else {
+            continue;
+        };
+        commands.entity(ent).despawn();
+        picked_writer.send(PowerUpPickedEvent(picker, *power_up));
+        let amount = pickups.power_up(*power_up).map_or(0, |def| def.amount);
+        // debuffs land on every snake except the one picking them up
+        let debuff = match power_up {
+            PowerUp::Projectile => {
+                let (_, _, mut launcher, ..) = heads.get_mut(picker).unwrap();
+                launcher.shots += amount;
+                None
+            }
+            PowerUp::Energy => {
+                let (_, _, _, mut dash, _) = heads.get_mut(picker).unwrap();
+                dash.add_charge(amount);
+                None
+            }
+            PowerUp::Shield => {
+                commands.entity(picker).insert(Shield);
+                None
+            }
+            PowerUp::Freeze => Some((StatusEffect::Frozen, amount)),
+            PowerUp::Confusion => Some((StatusEffect::Confused, amount)),
+        };
+        if let Some((effect, ticks)) = debuff {
+            for (other, _, _, _, mut effects) in heads.iter_mut() {
+                if other != picker {
+                    effects.apply(effect, ticks);
+                }
+            }
+        }
+    }
+}
+
+fn spawn_food(commands: &mut Commands, position: Position) -> Entity {
+    commands.spawn((Food, position)).id()
 }
 
 fn snake_eating(
     mut commands: Commands,
     mut growth_writer: EventWriter<GrowthEvent>,
+    mut score_writer: EventWriter<ScoreEvent>,
     food_positions: Query<(Entity, &Position), With<Food>>,
-    head_positions: Query<&Position, With<SnakeHead>>,
+    head_positions: Query<(Entity, &Position), With<SnakeHead>>,
 ) {
-    for head_pos in head_positions.iter() {
-        for (ent, food_pos) in food_positions.iter() {
-            if food_pos == head_pos {
-                commands.entity(ent).despawn();
-                growth_writer.send(GrowthEvent);
-            }
+    for (ent, food_pos) in food_positions.iter() {
+        if let Some((head_entity, _)) = head_positions
+            .iter()
+            .find(|(_, head_pos)| *head_pos == food_pos)
+        {
+            commands.entity(ent).despawn();
+            growth_writer.send(GrowthEvent(head_entity));
+            score_writer.send(ScoreEvent {
+                source: ScoreSource::Food,
+                points: 1,
+            });
         }
     }
 }
 
 fn snake_growth(
-    commands: Commands,
-    last_tail_position: Res<LastTailPosition>,
-    mut segments: ResMut<SnakeSegments>,
+    mut commands: Commands,
+    mut heads: Query<(&SnakeHead, &mut SnakeSegments, &LastTailPosition)>,
     mut growth_reader: EventReader<GrowthEvent>,
 ) {
-    if growth_reader.read().next().is_some() {
+    for GrowthEvent(head_entity) in growth_reader.read() {
+        let Ok((head, mut segments, last_tail_position)) = heads.get_mut(*head_entity) else {
+            continue;
+        };
+        // food on a spawn cell is eaten before the snake has moved, with no
+        // cell behind the tail to grow into yet
+        let Some(position) = last_tail_position.0 else {
+            continue;
+        };
+        let (_, segment_color) = head.player.colors();
         segments
             .0
-            .push(spawn_snake_segment(commands, last_tail_position.0.unwrap()));
+            .push(spawn_snake_segment(&mut commands, segment_color, position));
     }
 }
 
+fn trail_collision(
+    mode: Res<GameMode>,
+    trails: Query<&Position, With<Trail>>,
+    heads: Query<(Entity, &Position), With<SnakeHead>>,
+    mut died_writer: EventWriter<SnakeDiedEvent>,
+) {
+    if *mode != GameMode::Tron {
+        return;
+    }
+
+    for (head_entity, head_pos) in heads.iter() {
+        let hit_trail = trails.iter().any(|pos| pos == head_pos);
+        let hit_head = heads
+            .iter()
+            .any(|(other, other_pos)| other != head_entity && other_pos == head_pos);
+        if hit_trail || hit_head {
+            died_writer.send(SnakeDiedEvent(head_entity));
+        }
+    }
+}
+
+fn body_cutting(
+    mut commands: Commands,
+    mode: Res<GameMode>,
+    head_positions: Query<(Entity, &Position), With<SnakeHead>>,
+    mut snakes: Query<(Entity, &mut SnakeSegments, &mut LastTailPosition)>,
+    positions: Query<&Position>,
+    mut died_writer: EventWriter<SnakeDiedEvent>,
+    mut severed_writer: EventWriter<SeveredEvent>,
+) {
+    if *mode != GameMode::Versus {
+        return;
+    }
+
+    for (attacker, head_pos) in head_positions.iter() {
+        for (victim, mut segments, mut last_tail_position) in snakes.iter_mut() {
+            if victim == attacker {
+                continue;
+            }
+
+            // index of the victim's segment the attacker's head landed on
+            let Some(cut) = segments
+                .0
+                .iter()
+                .position(|ent| positions.get(*ent) == Ok(head_pos))
+            else {
+                continue;
+            };
+
+            // head on collisions are fatal, both heads will see each other
+            if cut == 0 {
+                died_writer.send(SnakeDiedEvent(victim));
+                continue;
+            }
+
+            sever_segments(
+                &mut commands,
+                &mut segments,
+                &mut last_tail_position,
+                &positions,
+                cut,
+            );
+            severed_writer.send(SeveredEvent(victim));
+        }
+    }
+}
+
+// Everything from `cut` back to the tail turns into food.
+fn sever_segments(
+    commands: &mut Commands,
+    segments: &mut SnakeSegments,
+    last_tail_position: &mut LastTailPosition,
+    positions: &Query<&Position>,
+    cut: usize,
+) {
+    for (i, ent) in segments.0.drain(cut..).enumerate() {
+        let pos = *positions.get(ent).unwrap();
+        if i == 0 {
+            *last_tail_position = LastTailPosition(Some(pos));
+        }
+        commands.entity(ent).despawn();
+        spawn_food(commands, pos);
+    }
+}
+
+fn snake_death(
+    mut commands: Commands,
+    mode: Res<GameMode>,
+    mut reader: EventReader<SnakeDiedEvent>,
+    mut lives: ResMut<Lives>,
+    mut death_camera: ResMut<death_camera::DeathCamera>,
+    heads: Query<(&SnakeHead, &SnakeSegments, &LastTailPosition, Has<Shield>)>,
+    mut positions: Query<&mut Position>,
+) {
+    let mut died = Vec::new();
+    for SnakeDiedEvent(head_entity) in reader.read() {
+        if !died.contains(head_entity) {
+            died.push(*head_entity);
+        }
+    }
+
+    for head_entity in died {
+        let Ok((head, segments, last_tail_position, shielded)) = heads.get(head_entity) else {
+            continue;
+        };
+
+        // a shield pops instead, stepping the snake back to where it was
+        // before this tick so the player gets a chance to turn away
+        if shielded {
+            commands.entity(head_entity).remove::<Shield>();
+            let previous: Vec<Position> = segments
+                .0
+                .iter()
+                .skip(1)
+                .map(|ent| *positions.get(*ent).unwrap())
+                .chain(last_tail_position.0)
+                .collect();
+            for (ent, pos) in segments.0.iter().zip(previous) {
+                *positions.get_mut(*ent).unwrap() = pos;
+            }
+            spawn_pop(&mut commands, *positions.get(head_entity).unwrap());
+            continue;
+        }
+
+        let shared_lives = !matches!(*mode, GameMode::Tron | GameMode::Versus);
+        // the run ends once the death camera has played it out
+        if shared_lives && lives.0 == 0 {
+            death_camera.start(*positions.get(head_entity).unwrap());
+            return;
+        }
+        for ent in segments.0.iter() {
+            commands.entity(*ent).despawn();
+        }
+        match *mode {
+            // eliminated light cycles stay out, leaving their trail behind
+            GameMode::Tron => {}
+            GameMode::Versus => spawn_snake(&mut commands, head.player),
+            GameMode::Classic | GameMode::Coop | GameMode::Arcade | GameMode::Objective => {
+                lives.0 -= 1;
+                spawn_snake(&mut commands, head.player);
+            }
+        }
+    }
+}
+
+fn coop_goal(
+    mut commands: Commands,
+    locale: Res<Locale>,
+    mode: Res<GameMode>,
+    score: Res<Score>,
+    mut game_over_writer: EventWriter<GameOverEvent>,
+) {
+    if *mode == GameMode::Coop && score.is_changed() && score.0 >= COOP_SCORE_GOAL {
+        show_toast(&mut commands, locale.text("toast-team-goal"));
+        game_over_writer.send(GameOverEvent);
+    }
+}
+
+fn tron_last_alive(
+    mut commands: Commands,
+    locale: Res<Locale>,
+    mode: Res<GameMode>,
+    heads: Query<&SnakeHead>,
+    mut game_over_writer: EventWriter<GameOverEvent>,
+) {
+    if *mode != GameMode::Tron || heads.iter().count() > 1 {
+        return;
+    }
+
+    let message = match heads.iter().next() {
+        Some(winner) => locale.format("toast-winner", &[("player", &winner.player.name(&locale))]),
+        None => locale.text("toast-draw").to_string(),
+    };
+    show_toast(&mut commands, message);
+    game_over_writer.send(GameOverEvent);
+}
+
 fn game_over(
     mut commands: Commands,
     mut reader: EventReader<GameOverEvent>,
-    segments_res: ResMut<SnakeSegments>,
-    food: Query<Entity, With<Food>>,
+    mode: Res<GameMode>,
+    mut score: ResMut<Score>,
+    mut lives: ResMut<Lives>,
+    items: Query<Entity, ArenaItems>,
     segments: Query<Entity, With<SnakeSegment>>,
 ) {
     if reader.read().next().is_some() {
-        for ent in food.iter().chain(segments.iter()) {
+        for ent in items.iter().chain(segments.iter()) {
             commands.entity(ent).despawn();
         }
-        spawn_snake(commands, segments_res);
+        *score = Score::default();
+        *lives = Lives(mode.lives());
+        for player in mode.players() {
+            spawn_snake(&mut commands, *player);
+        }
     }
 }
@@ -0,0 +1,253 @@
+use bevy::prelude::*;
+use rand::prelude::random;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    locale::Locale, progress::Progress, show_toast, Direction, GameMode, GameOverEvent,
+    GrowthEvent, Menu, SnakeHead, SnakeSegments,
+};
+
+const ACTIVE_MISSIONS: usize = 3;
+const NO_LEFT_TURN_MIN_LENGTH: usize = 8;
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Mission {
+    EatFood,
+    ReachLength,
+    NoLeftTurns,
+    VersusRuns,
+    TronRuns,
+}
+
+impl Mission {
+    const ALL: [Self; 5] = [
+        Self::EatFood,
+        Self::ReachLength,
+        Self::NoLeftTurns,
+        Self::VersusRuns,
+        Self::TronRuns,
+    ];
+
+    fn description(self, locale: &Locale) -> String {
+        let key = match self {
+            Self::EatFood => "mission-eat-food",
+            Self::ReachLength => "mission-reach-length",
+            Self::NoLeftTurns => "mission-no-left-turns",
+            Self::VersusRuns => "mission-versus-runs",
+            Self::TronRuns => "mission-tron-runs",
+        };
+        locale.format(
+            key,
+            &[
+                ("target", &self.target()),
+                ("length", &NO_LEFT_TURN_MIN_LENGTH),
+            ],
+        )
+    }
+
+    fn target(self) -> u32 {
+        match self {
+            Self::EatFood => 50,
+            Self::ReachLength => 12,
+            Self::NoLeftTurns => 3,
+            Self::VersusRuns => 5,
+            Self::TronRuns => 5,
+        }
+    }
+
+    fn reward(self) -> u32 {
+        match self {
+            Self::EatFood => 40,
+            Self::ReachLength => 60,
+            Self::NoLeftTurns => 100,
+            Self::VersusRuns | Self::TronRuns => 30,
+        }
+    }
+}
+
+// What the current run has done so far, for missions judged at its end.
+#[derive(Resource, Default)]
+struct RunTracker {
+    longest: usize,
+    turned_left: bool,
+    headings: Vec<(Entity, Direction)>,
+}
+
+#[derive(Component)]
+struct MissionBoard;
+
+pub struct MissionsPlugin;
+
+impl Plugin for MissionsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(RunTracker::default()).add_systems(
+            Update,
+            (
+                track_turns,
+                track_missions,
+                complete_missions,
+                toggle_mission_board,
+                update_mission_board,
+            )
+                .chain(),
+        );
+    }
+}
+
+fn is_left_turn(from: Direction, to: Direction) -> bool {
+    matches!(
+        (from, to),
+        (Direction::Up, Direction::Left)
+            | (Direction::Left, Direction::Down)
+            | (Direction::Down, Direction::Right)
+            | (Direction::Right, Direction::Up)
+    )
+}
+
+fn track_turns(mut tracker: ResMut<RunTracker>, heads: Query<(Entity, &SnakeHead)>) {
+    for (head_entity, head) in heads.iter() {
+        let previous = tracker
+            .headings
+            .iter()
+            .find(|(ent, _)| *ent == head_entity)
+            .map(|(_, dir)| *dir);
+        match previous {
+            Some(dir) if dir == head.direction => {}
+            Some(dir) => {
+                if is_left_turn(dir, head.direction) {
+                    tracker.turned_left = true;
+                }
+                tracker.headings.retain(|(ent, _)| *ent != head_entity);
+                tracker.headings.push((head_entity, head.direction));
+            }
+            None => tracker.headings.push((head_entity, head.direction)),
+        }
+    }
+}
+
+fn track_missions(
+    mode: Res<GameMode>,
+    mut progress: ResMut<Progress>,
+    mut tracker: ResMut<RunTracker>,
+    mut growth_reader: EventReader<GrowthEvent>,
+    mut game_over_reader: EventReader<GameOverEvent>,
+    heads: Query<&SnakeSegments, With<SnakeHead>>,
+) {
+    // keep a full board of missions, replacing any that were completed
+    while progress.missions.len() < ACTIVE_MISSIONS {
+        let available: Vec<Mission> = Mission::ALL
+            .into_iter()
+            .filter(|mission| !progress.missions.iter().any(|(m, _)| m == mission))
+            .collect();
+        let mission = available[(random::<f32>() * available.len() as f32) as usize];
+        progress.missions.push((mission, 0));
+    }
+
+    let eaten = growth_reader.read().count() as u32;
+    if let Some(length) = heads.iter().map(|segments| segments.0.len()).max() {
+        tracker.longest = tracker.longest.max(length);
+    }
+    let runs = game_over_reader.read().count() as u32;
+    if eaten == 0 && runs == 0 {
+        return;
+    }
+
+    let longest = tracker.longest as u32;
+    let clean_run = runs > 0 && !tracker.turned_left && tracker.longest >= NO_LEFT_TURN_MIN_LENGTH;
+    for (mission, count) in progress.missions.iter_mut() {
+        match mission {
+            Mission::EatFood => *count += eaten,
+            Mission::ReachLength => *count = (*count).max(longest),
+            Mission::NoLeftTurns if clean_run => *count += 1,
+            Mission::VersusRuns if *mode == GameMode::Versus => *count += runs,
+            Mission::TronRuns if *mode == GameMode::Tron => *count += runs,
+            _ => {}
+        }
+    }
+    if runs > 0 {
+        *tracker = RunTracker::default();
+        progress.save();
+    }
+}
+
+fn complete_missions(mut commands: Commands, locale: Res<Locale>, mut progress: ResMut<Progress>) {
+    let completed: Vec<Mission> = progress
+        .missions
+        .iter()
+        .filter(|(mission, count)| *count >= mission.target())
+        .map(|(mission, _)| *mission)
+        .collect();
+    for mission in completed.iter() {
+        progress.coins += mission.reward();
+        show_toast(
+            &mut commands,
+            locale.format(
+                "mission-complete",
+                &[
+                    ("mission", &mission.description(&locale)),
+                    ("reward", &mission.reward()),
+                ],
+            ),
+        );
+    }
+    if !completed.is_empty() {
+        progress
+            .missions
+            .retain(|(mission, _)| !completed.contains(mission));
+        progress.save();
+    }
+}
+
+fn toggle_mission_board(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    board: Query<Entity, With<MissionBoard>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::KeyM) {
+        return;
+    }
+
+    match board.get_single() {
+        Ok(ent) => commands.entity(ent).despawn(),
+        Err(_) => {
+            commands.spawn((
+                Text::default(),
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(5.0),
+                    right: Val::Px(5.0),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+                MissionBoard,
+                Menu,
+            ));
+        }
+    }
+}
+
+fn update_mission_board(
+    locale: Res<Locale>,
+    progress: Res<Progress>,
+    mut board: Query<&mut Text, With<MissionBoard>>,
+) {
+    const BAR_WIDTH: u32 = 10;
+
+    for mut text in board.iter_mut() {
+        let mut lines = vec![locale.text("missions-title").to_string()];
+        for (mission, count) in progress.missions.iter() {
+            let target = mission.target();
+            let filled = (count * BAR_WIDTH / target).min(BAR_WIDTH);
+            lines.push(format!(
+                "{}\n[{}{}] {}/{} - {}",
+                mission.description(&locale),
+                "#".repeat(filled as usize),
+                "-".repeat((BAR_WIDTH - filled) as usize),
+                (*count).min(target),
+                target,
+                locale.format("mission-reward", &[("coins", &mission.reward())])
+            ));
+        }
+        text.0 = lines.join("\n");
+    }
+}
@@ -0,0 +1,118 @@
+use bevy::prelude::*;
+use rand::Rng;
+use std::f32::consts::FRAC_PI_2;
+
+use crate::{
+    arg_value, locale::Locale, show_toast, tail_camera::MainCamera, Direction, SnakeHead,
+    SnakeSegment,
+};
+
+const CAMERA_TURN_SECS: f32 = 8.0;
+const CAMERA_TURN_SPEED: f32 = 2.0;
+
+// Optional twists on a run, picked on the command line,
+// e.g. `--modifiers mirror,spin,invisible-tail,double-food`.
+#[derive(Resource, Default)]
+pub struct Modifiers {
+    mirrored: bool,
+    spinning_camera: bool,
+    invisible_tail: bool,
+    pub double_food: bool,
+}
+
+impl Modifiers {
+    fn from_args() -> Self {
+        let mut modifiers = Self::default();
+        let Some(names) = arg_value("--modifiers") else {
+            return modifiers;
+        };
+        for name in names.split(',').map(str::trim) {
+            match name {
+                "mirror" => modifiers.mirrored = true,
+                "spin" => modifiers.spinning_camera = true,
+                "invisible-tail" => modifiers.invisible_tail = true,
+                "double-food" => modifiers.double_food = true,
+                _ => warn!("Ignoring unknown modifier {name:?}"),
+            }
+        }
+        modifiers
+    }
+
+    fn names(&self, locale: &Locale) -> Vec<String> {
+        [
+            (self.mirrored, "modifier-mirror"),
+            (self.spinning_camera, "modifier-spin"),
+            (self.invisible_tail, "modifier-invisible-tail"),
+            (self.double_food, "modifier-double-food"),
+        ]
+        .into_iter()
+        .filter(|(on, _)| *on)
+        .map(|(_, key)| locale.text(key).to_string())
+        .collect()
+    }
+
+    // Left and right swap places while mirrored.
+    pub fn steer(&self, direction: Direction) -> Direction {
+        match direction {
+            Direction::Left | Direction::Right if self.mirrored => direction.opposite(),
+            _ => direction,
+        }
+    }
+}
+
+pub struct ModifiersPlugin;
+
+impl Plugin for ModifiersPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Modifiers::from_args())
+            .add_systems(Startup, announce_modifiers)
+            .add_systems(Update, (spin_camera, hide_tail));
+    }
+}
+
+fn announce_modifiers(mut commands: Commands, locale: Res<Locale>, modifiers: Res<Modifiers>) {
+    let names = modifiers.names(&locale);
+    if !names.is_empty() {
+        show_toast(
+            &mut commands,
+            locale.format("toast-modifiers", &[("modifiers", &names.join(", "))]),
+        );
+    }
+}
+
+// Every so often the arena turns to a new random quarter and the camera
+// swings round to it.
+fn spin_camera(
+    time: Res<Time>,
+    modifiers: Res<Modifiers>,
+    mut timer: Local<Option<Timer>>,
+    mut angles: Local<(f32, f32)>,
+    mut camera: Single<&mut Transform, MainCamera>,
+) {
+    if !modifiers.spinning_camera {
+        return;
+    }
+
+    let timer =
+        timer.get_or_insert_with(|| Timer::from_seconds(CAMERA_TURN_SECS, TimerMode::Repeating));
+    let (angle, target) = &mut *angles;
+    if timer.tick(time.delta()).just_finished() {
+        *target = rand::thread_rng().gen_range(0..4) as f32 * FRAC_PI_2;
+    }
+    *angle += (*target - *angle) * (CAMERA_TURN_SPEED * time.delta_secs()).min(1.0);
+    camera.rotation = Quat::from_rotation_z(*angle);
+}
+
+fn hide_tail(
+    mut commands: Commands,
+    modifiers: Res<Modifiers>,
+    segments: Query<Entity, (Added<SnakeSegment>, Without<SnakeHead>)>,
+) {
+    if !modifiers.invisible_tail {
+        return;
+    }
+
+    for ent in segments.iter() {
+        commands.entity(ent).insert(Visibility::Hidden);
+    }
+}
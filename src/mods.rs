@@ -0,0 +1,354 @@
+use bevy::prelude::*;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use rhai::{CallFnOptions, Dynamic, Engine, Map, Scope, AST};
+use std::{
+    fs,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use crate::{
+    brains::Brains, event_feed::FeedEvent, locale::Locale, profiles::base_dir, show_toast,
+    snake_death, snake_movement, spawn_food, FixedTimer, GameRng, GrowthEvent, LastTailPosition,
+    Occupied, Player, Position, SnakeDiedEvent, SnakeHead, SnakeSegments, ARENA_HEIGHT,
+    ARENA_WIDTH,
+};
+
+// Keeps a runaway script from freezing the game.
+const MAX_OPERATIONS: u64 = 100_000;
+const MIN_TICK_MILLIS: i64 = 50;
+const MAX_TICK_MILLIS: i64 = 2000;
+
+// What scripts ask the game to do, applied once the hooks have run.
+enum ModCommand {
+    SpawnFood(Position),
+    SetSpeed(Duration),
+    Teleport(Player, Position),
+    SetBrain(Player, String),
+    Toast(String),
+    Log(String),
+}
+
+struct Script {
+    name: String,
+    ast: AST,
+    // bound as `this` in every hook so scripts can keep state between calls
+    state: Dynamic,
+}
+
+// Rule mods written in Rhai, loaded from the mods folder next to the saves.
+// Scripts define any of `on_tick()`, `on_eat(player, length)` and
+// `on_death(player)`, and call `spawn_food(x, y)`, `set_speed(millis)`,
+// `teleport(player, x, y)`, `set_brain(player, name)`, `toast(text)`,
+// `log(text)` and `random(n)` to change the game.
+#[derive(Resource)]
+struct Mods {
+    engine: Engine,
+    scripts: Vec<Script>,
+    queue: Arc<Mutex<Vec<ModCommand>>>,
+    // what `random(n)` draws from, reseeded from the game's own RNG before
+    // the hooks run so seeded runs play out the same with mods loaded
+    rng: Arc<Mutex<ChaCha8Rng>>,
+}
+
+impl Mods {
+    fn load() -> Self {
+        let queue = Arc::new(Mutex::new(Vec::new()));
+        let rng = Arc::new(Mutex::new(ChaCha8Rng::seed_from_u64(0)));
+        let engine = Self::engine(&queue, &rng);
+
+        let mut paths: Vec<_> = base_dir()
+            .and_then(|dir| fs::read_dir(dir.join("mods")).ok())
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| Some(entry.ok()?.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "rhai"))
+            .collect();
+        paths.sort();
+
+        let scripts = paths
+            .into_iter()
+            .filter_map(|path| {
+                let name = path.file_stem()?.to_string_lossy().into_owned();
+                match engine.compile_file(path) {
+                    Ok(ast) => Some(Script {
+                        name,
+                        ast,
+                        state: Dynamic::from_map(Map::new()),
+                    }),
+                    Err(err) => {
+                        warn!("Skipping mod {name}: {err}");
+                        None
+                    }
+                }
+            })
+            .collect();
+
+        Self {
+            engine,
+            scripts,
+            queue,
+            rng,
+        }
+    }
+
+    fn engine(queue: &Arc<Mutex<Vec<ModCommand>>>, rng: &Arc<Mutex<ChaCha8Rng>>) -> Engine {
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+
+        let push = |queue: &Arc<Mutex<Vec<ModCommand>>>| {
+            let queue = queue.clone();
+            move |command| queue.lock().unwrap().push(command)
+        };
+        let send = push(queue);
+        engine.register_fn("spawn_food", move |x: i64, y: i64| {
+            send(ModCommand::SpawnFood(Position {
+                x: x as i32,
+                y: y as i32,
+            }))
+        });
+        let send = push(queue);
+        engine.register_fn("set_speed", move |millis: i64| {
+            let millis = millis.clamp(MIN_TICK_MILLIS, MAX_TICK_MILLIS) as u64;
+            send(ModCommand::SetSpeed(Duration::from_millis(millis)))
+        });
+        let send = push(queue);
+        engine.register_fn("teleport", move |player: i64, x: i64, y: i64| {
+            let player = player_from_number(player);
+            let position = Position {
+                x: x as i32,
+                y: y as i32,
+            };
+            send(ModCommand::Teleport(player, position))
+        });
+        let send = push(queue);
+        engine.register_fn("set_brain", move |player: i64, name: &str| {
+            send(ModCommand::SetBrain(
+                player_from_number(player),
+                name.to_string(),
+            ))
+        });
+        let send = push(queue);
+        engine.register_fn("toast", move |text: &str| {
+            send(ModCommand::Toast(text.to_string()))
+        });
+        let send = push(queue);
+        engine.register_fn("log", move |text: &str| {
+            send(ModCommand::Log(text.to_string()))
+        });
+        let rng = rng.clone();
+        engine.register_fn("random", move |n: i64| {
+            if n <= 0 {
+                return 0;
+            }
+            rng.lock().unwrap().gen_range(0..n)
+        });
+        engine.register_fn("arena_width", || ARENA_WIDTH as i64);
+        engine.register_fn("arena_height", || ARENA_HEIGHT as i64);
+        engine
+    }
+
+    // Runs `hook` in every script that defines it.
+    fn call(&mut self, hook: &str, args: &[i64]) {
+        for script in self.scripts.iter_mut() {
+            if !script
+                .ast
+                .iter_functions()
+                .any(|f| f.name == hook && f.params.len() == args.len())
+            {
+                continue;
+            }
+            let options = CallFnOptions::new()
+                .eval_ast(false)
+                .bind_this_ptr(&mut script.state);
+            if let Err(err) = self.engine.call_fn_with_options::<Dynamic>(
+                options,
+                &mut Scope::new(),
+                &script.ast,
+                hook,
+                args.iter()
+                    .map(|arg| Dynamic::from(*arg))
+                    .collect::<Vec<_>>(),
+            ) {
+                warn!("Mod {} failed in {hook}: {err}", script.name);
+            }
+        }
+    }
+}
+
+fn player_number(player: Player) -> i64 {
+    match player {
+        Player::One => 1,
+        Player::Two => 2,
+    }
+}
+
+fn player_from_number(number: i64) -> Player {
+    if number == 2 {
+        Player::Two
+    } else {
+        Player::One
+    }
+}
+
+pub struct ModsPlugin;
+
+impl Plugin for ModsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Mods::load())
+            .add_systems(Startup, announce_mods)
+            .add_systems(
+                Update,
+                (run_mod_hooks, apply_mod_commands)
+                    .chain()
+                    .after(snake_movement)
+                    .before(snake_death),
+            );
+    }
+}
+
+fn announce_mods(mut commands: Commands, locale: Res<Locale>, mods: Res<Mods>) {
+    if !mods.scripts.is_empty() {
+        show_toast(
+            &mut commands,
+            locale.format("toast-mods-loaded", &[("count", &mods.scripts.len())]),
+        );
+    }
+}
+
+fn run_mod_hooks(
+    mut mods: ResMut<Mods>,
+    timer: Res<FixedTimer>,
+    mut game_rng: ResMut<GameRng>,
+    mut growth_reader: EventReader<GrowthEvent>,
+    mut died_reader: EventReader<SnakeDiedEvent>,
+    heads: Query<(&SnakeHead, &SnakeSegments)>,
+) {
+    if mods.scripts.is_empty()
+        || (!timer.0.just_finished() && growth_reader.is_empty() && died_reader.is_empty())
+    {
+        return;
+    }
+
+    *mods.rng.lock().unwrap() = ChaCha8Rng::seed_from_u64(game_rng.0.gen());
+
+    if timer.0.just_finished() {
+        mods.call("on_tick", &[]);
+    }
+    for GrowthEvent(head) in growth_reader.read() {
+        if let Ok((head, segments)) = heads.get(*head) {
+            let args = [player_number(head.player), segments.0.len() as i64];
+            mods.call("on_eat", &args);
+        }
+    }
+    for SnakeDiedEvent(head) in died_reader.read() {
+        if let Ok((head, _)) = heads.get(*head) {
+            mods.call("on_death", &[player_number(head.player)]);
+        }
+    }
+}
+
+// Everything a snake could land on, and every position to move it with.
+type Positions<'w, 's> = ParamSet<
+    'w,
+    's,
+    (
+        Query<'static, 'static, (Entity, &'static Position), Occupied>,
+        Query<'static, 'static, &'static mut Position>,
+    ),
+>;
+
+// Moves the whole snake so its head lands on `target`, keeping its shape.
+// Refused if any of it would end up off the arena or on something else.
+fn teleport_snake(
+    positions: &mut Positions,
+    segments: &[Entity],
+    last_tail_position: &mut LastTailPosition,
+    target: Position,
+) -> bool {
+    let Ok(head) = positions.p1().get(segments[0]).copied() else {
+        return false;
+    };
+    let shift = |pos: Position| Position {
+        x: pos.x + target.x - head.x,
+        y: pos.y + target.y - head.y,
+    };
+    let moved: Vec<Position> = segments
+        .iter()
+        .filter_map(|ent| positions.p1().get(*ent).ok().copied().map(shift))
+        .collect();
+    let blocked = positions
+        .p0()
+        .iter()
+        .any(|(ent, pos)| !segments.contains(&ent) && moved.contains(pos));
+    if blocked || !moved.iter().all(|pos| pos.in_arena()) {
+        return false;
+    }
+
+    let mut all = positions.p1();
+    for (ent, pos) in segments.iter().zip(moved) {
+        if let Ok(mut position) = all.get_mut(*ent) {
+            *position = pos;
+        }
+    }
+    last_tail_position.0 = last_tail_position.0.map(shift).filter(|pos| pos.in_arena());
+    true
+}
+
+fn apply_mod_commands(
+    mut commands: Commands,
+    mods: Res<Mods>,
+    mut timer: ResMut<FixedTimer>,
+    mut brains: ResMut<Brains>,
+    mut heads: Query<(&SnakeHead, &SnakeSegments, &mut LastTailPosition)>,
+    mut positions: Positions,
+    mut feed_writer: EventWriter<FeedEvent>,
+) {
+    let queued = std::mem::take(&mut *mods.queue.lock().unwrap());
+    // food spawned by this batch, which the occupied query can't see yet
+    let mut spawned = Vec::new();
+    for command in queued {
+        match command {
+            ModCommand::SpawnFood(position) => {
+                let occupied = spawned.contains(&position)
+                    || positions.p0().iter().any(|(_, pos)| *pos == position);
+                if occupied || !position.in_arena() {
+                    warn!(
+                        "Mod food at {}, {} is off the arena or on something else",
+                        position.x, position.y
+                    );
+                    continue;
+                }
+                spawn_food(&mut commands, position);
+                spawned.push(position);
+            }
+            ModCommand::SetSpeed(duration) => timer.0.set_duration(duration),
+            ModCommand::Teleport(player, position) => {
+                let Some((_, segments, mut last_tail_position)) =
+                    heads.iter_mut().find(|(head, _, _)| head.player == player)
+                else {
+                    continue;
+                };
+                if !teleport_snake(
+                    &mut positions,
+                    &segments.0,
+                    &mut last_tail_position,
+                    position,
+                ) {
+                    warn!(
+                        "Mod teleport to {}, {} doesn't fit the snake",
+                        position.x, position.y
+                    );
+                }
+            }
+            ModCommand::SetBrain(player, name) => brains.assign(player, &name),
+            ModCommand::Toast(text) => {
+                show_toast(&mut commands, text);
+            }
+            ModCommand::Log(text) => {
+                feed_writer.send(FeedEvent(text));
+            }
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use bevy::prelude::*;
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    arcade::Stage, locale::Locale, score_breakdown::tally_score, snake_growth, spawn_snake_segment,
+    FixedTimer, GameRng, GrowthEvent, LastTailPosition, ScoreEvent, ScoreSource, Shield, SnakeHead,
+    SnakeSegment, SnakeSegments,
+};
+
+// Mutations offered at once in a draft.
+pub const DRAFT_SIZE: usize = 3;
+
+// Roguelite twists drafted between arcade stages, each a trade-off that
+// lasts for the rest of the run.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Mutation {
+    // faster, but every food grows the snake by three
+    Haste,
+    // double score, but food turns up half as often
+    Greed,
+    // double score, but the body can't be seen
+    Fog,
+    // a shield on every respawn, but no more bonus lives
+    Bulwark,
+    // slower, but stages take more food to clear
+    Sloth,
+}
+
+impl Mutation {
+    const ALL: [Self; 5] = [
+        Self::Haste,
+        Self::Greed,
+        Self::Fog,
+        Self::Bulwark,
+        Self::Sloth,
+    ];
+
+    pub fn name(self, locale: &Locale) -> &str {
+        locale.text(match self {
+            Self::Haste => "mutation-haste",
+            Self::Greed => "mutation-greed",
+            Self::Fog => "mutation-fog",
+            Self::Bulwark => "mutation-bulwark",
+            Self::Sloth => "mutation-sloth",
+        })
+    }
+
+    pub fn description(self, locale: &Locale) -> &str {
+        locale.text(match self {
+            Self::Haste => "mutation-haste-description",
+            Self::Greed => "mutation-greed-description",
+            Self::Fog => "mutation-fog-description",
+            Self::Bulwark => "mutation-bulwark-description",
+            Self::Sloth => "mutation-sloth-description",
+        })
+    }
+
+    pub fn tick_factor(self) -> f32 {
+        match self {
+            Self::Haste => 0.8,
+            Self::Sloth => 1.25,
+            _ => 1.0,
+        }
+    }
+
+    pub fn food_spawn_factor(self) -> f32 {
+        match self {
+            Self::Greed => 2.0,
+            _ => 1.0,
+        }
+    }
+
+    pub fn extra_stage_food(self) -> u32 {
+        match self {
+            Self::Sloth => 5,
+            _ => 0,
+        }
+    }
+
+    fn score_multiplier(self) -> u32 {
+        match self {
+            Self::Greed | Self::Fog => 2,
+            _ => 1,
+        }
+    }
+
+    fn extra_growth(self) -> u32 {
+        match self {
+            Self::Haste => 2,
+            _ => 0,
+        }
+    }
+
+    // Up to DRAFT_SIZE mutations the run doesn't have yet.
+    pub fn draft(taken: &[Self], rng: &mut GameRng) -> Vec<Self> {
+        let mut left: Vec<Self> = Self::ALL
+            .into_iter()
+            .filter(|mutation| !taken.contains(mutation))
+            .collect();
+        left.shuffle(&mut rng.0);
+        left.truncate(DRAFT_SIZE);
+        left
+    }
+}
+
+pub struct MutationsPlugin;
+
+impl Plugin for MutationsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                (score_bonus.before(tally_score), extra_growth).after(snake_growth),
+                hide_body,
+                shield_on_spawn,
+            ),
+        );
+    }
+}
+
+fn score_bonus(
+    stage: Res<Stage>,
+    mut score_writer: EventWriter<ScoreEvent>,
+    mut growth_reader: EventReader<GrowthEvent>,
+) {
+    let eaten = growth_reader.read().count() as u32;
+    let multiplier: u32 = stage
+        .mutations
+        .iter()
+        .map(|m| m.score_multiplier())
+        .product();
+    if eaten > 0 && multiplier > 1 {
+        score_writer.send(ScoreEvent {
+            source: ScoreSource::MutationBonus,
+            points: eaten * (multiplier - 1),
+        });
+    }
+}
+
+// Growth beyond the usual segment comes one per tick after the food, each
+// new segment taking the cell the tail has just left.
+fn extra_growth(
+    mut commands: Commands,
+    timer: Res<FixedTimer>,
+    mut stage: ResMut<Stage>,
+    mut growth_reader: EventReader<GrowthEvent>,
+    mut heads: Query<(&SnakeHead, &mut SnakeSegments, &LastTailPosition)>,
+) {
+    let eaten = growth_reader.read().count() as u32;
+    if eaten > 0 {
+        let extra: u32 = stage.mutations.iter().map(|m| m.extra_growth()).sum();
+        stage.owed_growth += eaten * extra;
+        // the tail's last cell was just taken by the food's own segment
+        return;
+    }
+    if stage.owed_growth == 0 || !timer.0.just_finished() {
+        return;
+    }
+
+    for (head, mut segments, last_tail_position) in heads.iter_mut() {
+        let Some(position) = last_tail_position.0 else {
+            continue;
+        };
+        let (_, segment_color) = head.player.colors();
+        segments
+            .0
+            .push(spawn_snake_segment(&mut commands, segment_color, position));
+        stage.owed_growth -= 1;
+        break;
+    }
+}
+
+fn hide_body(
+    mut commands: Commands,
+    stage: Res<Stage>,
+    segments: Query<Entity, (Added<SnakeSegment>, Without<SnakeHead>)>,
+) {
+    if !stage.mutations.contains(&Mutation::Fog) {
+        return;
+    }
+
+    for ent in segments.iter() {
+        commands.entity(ent).insert(Visibility::Hidden);
+    }
+}
+
+fn shield_on_spawn(
+    mut commands: Commands,
+    stage: Res<Stage>,
+    heads: Query<Entity, Added<SnakeHead>>,
+) {
+    if !stage.mutations.contains(&Mutation::Bulwark) {
+        return;
+    }
+
+    for ent in heads.iter() {
+        commands.entity(ent).insert(Shield);
+    }
+}
@@ -0,0 +1,113 @@
+use bevy::prelude::*;
+
+use crate::{
+    event_feed::FeedEvent, food_placement::FoodPlacement, free_cells, locale::Locale, snake_eating,
+    snake_movement, spawn_food, Food, GameMode, GameRng, Occupied, Position, ScoreEvent,
+    ScoreSource, SnakeHead,
+};
+
+// Pellets laid out at once, labelled 1 to this.
+const PELLETS: u32 = 5;
+// Points for eating the lowest pellet left; any other is worth the usual one.
+const IN_ORDER_POINTS: u32 = 3;
+const LABEL_COLOR: Color = Color::srgb(0.05, 0.05, 0.05);
+
+// Order a food pellet should be eaten in, in objective mode.
+#[derive(Component)]
+pub struct Numbered(pub u32);
+
+// The number drawn over a pellet. Kept off the pellet itself since the
+// pellet's scale would blow the text up with it.
+#[derive(Component)]
+struct PelletLabel(Entity);
+
+pub struct ObjectivesPlugin;
+
+impl Plugin for ObjectivesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                score_pellets.after(snake_movement).before(snake_eating),
+                lay_out_pellets,
+                clear_labels,
+            ),
+        );
+    }
+}
+
+fn lay_out_pellets(
+    mut commands: Commands,
+    mode: Res<GameMode>,
+    placement: Res<FoodPlacement>,
+    mut rng: ResMut<GameRng>,
+    food: Query<(), With<Food>>,
+    occupied: Query<&Position, Occupied>,
+) {
+    if *mode != GameMode::Objective || !food.is_empty() {
+        return;
+    }
+
+    let mut free = free_cells(&occupied);
+    for number in 1..=PELLETS {
+        if free.is_empty() {
+            return;
+        }
+        let position = free.swap_remove(placement.pick(&free, &mut rng));
+        spawn_numbered_pellet(&mut commands, position, number);
+    }
+}
+
+// A pellet with its number drawn over it, laid out fresh or put back by a
+// resumed run.
+pub fn spawn_numbered_pellet(commands: &mut Commands, position: Position, number: u32) -> Entity {
+    let pellet = spawn_food(commands, position);
+    commands.entity(pellet).insert(Numbered(number));
+    commands.spawn((
+        Text2d::new(number.to_string()),
+        TextFont::from_font_size(24.0),
+        TextColor(LABEL_COLOR),
+        Transform::from_xyz(0.0, 0.0, 1.0),
+        position,
+        PelletLabel(pellet),
+    ));
+    pellet
+}
+
+// Runs before the pellets are eaten, which only scores the base point.
+fn score_pellets(
+    locale: Res<Locale>,
+    mut score_writer: EventWriter<ScoreEvent>,
+    mut feed_writer: EventWriter<FeedEvent>,
+    pellets: Query<(&Numbered, &Position), With<Food>>,
+    heads: Query<&Position, With<SnakeHead>>,
+) {
+    let lowest = pellets.iter().map(|(number, _)| number.0).min();
+    for (number, pos) in pellets.iter() {
+        if !heads.iter().any(|head_pos| head_pos == pos) {
+            continue;
+        }
+        if Some(number.0) == lowest {
+            score_writer.send(ScoreEvent {
+                source: ScoreSource::OrderBonus,
+                points: IN_ORDER_POINTS - 1,
+            });
+        } else {
+            feed_writer.send(FeedEvent(
+                locale.format("feed-out-of-order", &[("number", &number.0)]),
+            ));
+        }
+    }
+}
+
+fn clear_labels(
+    mut commands: Commands,
+    labels: Query<(Entity, &PelletLabel)>,
+    pellets: Query<(), With<Food>>,
+) {
+    for (ent, label) in labels.iter() {
+        if !pellets.contains(label.0) {
+            commands.entity(ent).despawn();
+        }
+    }
+}
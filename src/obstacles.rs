@@ -0,0 +1,170 @@
+use bevy::prelude::*;
+use rand::{seq::SliceRandom, Rng};
+use std::time::Duration;
+
+use crate::{
+    free_cells, planner, snake_death, snake_movement, GameMode, GameRng, Occupied, Position, Score,
+    Size, SnakeDiedEvent, SnakeHead, SnakeSegment, ARENA_HEIGHT, ARENA_WIDTH,
+};
+
+const BLOCK_COLOR: Color = Color::srgb(0.45, 0.45, 0.5);
+// Seconds between clusters at a score of zero, and the shortest it gets.
+const FIRST_CLUSTER_SECS: f32 = 20.0;
+const FASTEST_CLUSTER_SECS: f32 = 5.0;
+// Every this many points takes another share of the first interval off.
+const SCORE_PER_SPEEDUP: u32 = 10;
+// Clusters keep at least this many cells between themselves and a head.
+const HEAD_CLEARANCE: i32 = 3;
+// Walls never cover more than this share of the arena.
+const MAX_BLOCK_SHARE: f32 = 0.2;
+const PLACEMENT_ATTEMPTS: usize = 20;
+
+// Cell offsets of each cluster shape.
+const SHAPES: [&[(i32, i32)]; 5] = [
+    &[(0, 0), (1, 0)],
+    &[(0, 0), (0, 1)],
+    &[(0, 0), (1, 0), (2, 0)],
+    &[(0, 0), (0, 1), (0, 2)],
+    &[(0, 0), (1, 0), (0, 1)],
+];
+
+// Everything a snake can't move through, which a cluster mustn't wall in.
+pub type Blocking = Or<(With<SnakeSegment>, With<Block>)>;
+type BlockingCell = (Entity, &'static Position, Has<Block>, Has<SnakeHead>);
+
+// A wall cell raised during an endless run.
+#[derive(Component)]
+pub struct Block;
+
+#[derive(Resource)]
+struct ClusterTimer(Timer);
+
+pub struct ObstaclesPlugin;
+
+impl Plugin for ObstaclesPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ClusterTimer(Timer::from_seconds(
+            FIRST_CLUSTER_SECS,
+            TimerMode::Repeating,
+        )))
+        .add_systems(
+            Update,
+            (
+                (pace_clusters, raise_clusters).chain(),
+                block_collision.after(snake_movement).before(snake_death),
+            ),
+        );
+    }
+}
+
+pub fn spawn_block(commands: &mut Commands, position: Position) {
+    commands.spawn((
+        Sprite {
+            color: BLOCK_COLOR,
+            ..default()
+        },
+        Block,
+        position,
+        Size::square(1.0),
+    ));
+}
+
+// The better the run is going, the more often the walls close in.
+fn pace_clusters(score: Res<Score>, mut timer: ResMut<ClusterTimer>) {
+    if !score.is_changed() {
+        return;
+    }
+
+    let speedups = (score.0 / SCORE_PER_SPEEDUP) as f32;
+    let secs = (FIRST_CLUSTER_SECS / (1.0 + speedups)).max(FASTEST_CLUSTER_SECS);
+    timer.0.set_duration(Duration::from_secs_f32(secs));
+}
+
+// Only in classic, the endless mode. A cluster goes on free cells away
+// from the heads, and only where it doesn't cut a head off from any cell
+// it could reach before.
+fn raise_clusters(
+    mut commands: Commands,
+    time: Res<Time>,
+    mode: Res<GameMode>,
+    mut timer: ResMut<ClusterTimer>,
+    mut rng: ResMut<GameRng>,
+    occupied: Query<&Position, Occupied>,
+    blocking: Query<BlockingCell, Blocking>,
+) {
+    if *mode != GameMode::Classic || !timer.0.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    let free = free_cells(&occupied);
+    let heads: Vec<(Entity, Position)> = blocking
+        .iter()
+        .filter(|(.., is_head)| *is_head)
+        .map(|(ent, pos, ..)| (ent, *pos))
+        .collect();
+    let blocked: Vec<(Entity, Position)> =
+        blocking.iter().map(|(ent, pos, ..)| (ent, *pos)).collect();
+    let blocks = blocking
+        .iter()
+        .filter(|(_, _, is_block, _)| *is_block)
+        .count();
+    let max_blocks = (ARENA_WIDTH * ARENA_HEIGHT) as f32 * MAX_BLOCK_SHARE;
+    if free.is_empty() || blocks as f32 >= max_blocks {
+        return;
+    }
+
+    for _ in 0..PLACEMENT_ATTEMPTS {
+        let anchor = free[rng.0.gen_range(0..free.len())];
+        let shape = SHAPES.choose(&mut rng.0).unwrap();
+        let cluster: Vec<Position> = shape
+            .iter()
+            .map(|(dx, dy)| Position {
+                x: anchor.x + dx,
+                y: anchor.y + dy,
+            })
+            .collect();
+        let fits = cluster.iter().all(|cell| {
+            free.contains(cell)
+                && heads.iter().all(|(_, head)| {
+                    (cell.x - head.x).abs() + (cell.y - head.y).abs() > HEAD_CLEARANCE
+                })
+        });
+        if fits && !traps_a_snake(&cluster, &blocked, &heads) {
+            for cell in cluster {
+                spawn_block(&mut commands, cell);
+            }
+            return;
+        }
+    }
+}
+
+fn traps_a_snake(
+    cluster: &[Position],
+    blocked: &[(Entity, Position)],
+    heads: &[(Entity, Position)],
+) -> bool {
+    heads.iter().any(|(head_entity, start)| {
+        let mut blocked: Vec<Position> = blocked
+            .iter()
+            .filter(|(ent, _)| ent != head_entity)
+            .map(|(_, pos)| *pos)
+            .collect();
+        let before = planner::reachable(*start, &blocked);
+        blocked.extend_from_slice(cluster);
+        let after = planner::reachable(*start, &blocked);
+        // the cluster's own cells are the only ones it may take away
+        after.len() + cluster.iter().filter(|cell| before.contains(cell)).count() < before.len()
+    })
+}
+
+fn block_collision(
+    blocks: Query<&Position, With<Block>>,
+    heads: Query<(Entity, &Position), With<SnakeHead>>,
+    mut died_writer: EventWriter<SnakeDiedEvent>,
+) {
+    for (head_entity, head_pos) in heads.iter() {
+        if blocks.iter().any(|pos| pos == head_pos) {
+            died_writer.send(SnakeDiedEvent(head_entity));
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use bevy::{
+    prelude::*,
+    window::{Monitor, PrimaryMonitor},
+};
+
+use crate::{
+    locale::{Language, Locale},
+    only_menu,
+    progress::Progress,
+    Menu,
+};
+
+// Share of the monitor's shorter side the square window starts at.
+const WINDOW_SHARE: f32 = 0.6;
+const MIN_WINDOW_SIZE: f32 = 500.0;
+// Logical monitor heights from which the UI starts out scaled up.
+const UI_SCALE_STEPS: [(f32, f32); 2] = [(1400.0, 1.5), (1000.0, 1.25)];
+const CHOICE_KEYS: [KeyCode; 2] = [KeyCode::Digit1, KeyCode::Digit2];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Language,
+    Colors,
+}
+
+// The first-run questions, asked one at a time with the game paused.
+#[derive(Component)]
+struct Onboarding(Step);
+
+pub struct OnboardingPlugin;
+
+impl Plugin for OnboardingPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.world().resource::<Progress>().first_run {
+            return;
+        }
+
+        app.add_systems(Startup, start_onboarding).add_systems(
+            Update,
+            (fit_display, (answer_onboarding, update_onboarding).chain()),
+        );
+    }
+}
+
+fn start_onboarding(mut commands: Commands, mut time: ResMut<Time<Virtual>>) {
+    time.pause();
+    commands.spawn((
+        Text::default(),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Percent(35.0),
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        TextLayout::new_with_justify(JustifyText::Center),
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+        Onboarding(Step::Language),
+        Menu,
+    ));
+}
+
+// Sizes the window and UI to the monitor the first time one is known,
+// rather than the fixed 500x500 everyone starts with otherwise.
+fn fit_display(
+    mut done: Local<bool>,
+    mut progress: ResMut<Progress>,
+    monitors: Query<&Monitor, With<PrimaryMonitor>>,
+) {
+    if *done {
+        return;
+    }
+    let Ok(monitor) = monitors.get_single() else {
+        return;
+    };
+    *done = true;
+
+    let scale_factor = monitor.scale_factor as f32;
+    let width = monitor.physical_width as f32 / scale_factor;
+    let height = monitor.physical_height as f32 / scale_factor;
+    let side = (width.min(height) * WINDOW_SHARE).max(MIN_WINDOW_SIZE);
+    progress.display.set_size(Vec2::splat(side));
+    progress.ui_scale = UI_SCALE_STEPS
+        .iter()
+        .find(|(min_height, _)| height >= *min_height)
+        .map_or(1.0, |(_, scale)| *scale);
+}
+
+fn answer_onboarding(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut time: ResMut<Time<Virtual>>,
+    mut progress: ResMut<Progress>,
+    mut onboarding: Query<(Entity, &mut Onboarding)>,
+    menus: Query<Entity, With<Menu>>,
+) {
+    let Ok((ent, mut onboarding)) = onboarding.get_single_mut() else {
+        return;
+    };
+    if !only_menu(ent, &menus) {
+        return;
+    }
+    let Some(choice) = CHOICE_KEYS
+        .iter()
+        .position(|key| keyboard_input.just_pressed(*key))
+    else {
+        return;
+    };
+
+    match onboarding.0 {
+        Step::Language => {
+            progress.language = Language::ALL[choice];
+            onboarding.0 = Step::Colors;
+        }
+        Step::Colors => {
+            progress.colorblind = choice == 1;
+            // the first save is what stops this from being asked again
+            progress.save();
+            commands.entity(ent).despawn();
+            time.unpause();
+        }
+    }
+}
+
+fn update_onboarding(locale: Res<Locale>, mut onboarding: Query<(&Onboarding, &mut Text)>) {
+    for (onboarding, mut text) in onboarding.iter_mut() {
+        let lines = match onboarding.0 {
+            Step::Language => {
+                let mut lines = vec![locale.text("onboarding-welcome").to_string()];
+                for (i, language) in Language::ALL.iter().enumerate() {
+                    lines.push(format!("{}. {}", i + 1, language.native_name()));
+                }
+                lines
+            }
+            Step::Colors => vec![
+                locale.text("onboarding-colors").to_string(),
+                format!("1. {}", locale.text("onboarding-colors-standard")),
+                format!("2. {}", locale.text("onboarding-colors-colorblind")),
+            ],
+        };
+        text.0 = lines.join("\n");
+    }
+}
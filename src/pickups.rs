@@ -0,0 +1,150 @@
+use bevy::prelude::*;
+use rand::Rng;
+use serde::Deserialize;
+
+use crate::{
+    food_placement::FoodStrategy,
+    hot_reload::data_file,
+    progress::Progress,
+    seasons::{SeasonalFood, Seasons},
+    size_scaling, Food, GameMode, GameRng, PowerUp, Size,
+};
+
+#[derive(Deserialize)]
+pub struct PickupLook {
+    color: (f32, f32, f32),
+    // used instead of `color` for players who picked the colour-blind
+    // friendly palette
+    #[serde(default)]
+    colorblind_color: Option<(f32, f32, f32)>,
+    size: f32,
+}
+
+impl PickupLook {
+    pub fn color(&self) -> Color {
+        self.color_for(false)
+    }
+
+    pub fn color_for(&self, colorblind: bool) -> Color {
+        let (red, green, blue) = match self.colorblind_color {
+            Some(color) if colorblind => color,
+            _ => self.color,
+        };
+        Color::srgb(red, green, blue)
+    }
+}
+
+#[derive(Deserialize)]
+pub struct PowerUpDef {
+    // which handler in `power_up_pickup` runs when it is collected
+    pub effect: PowerUp,
+    pub look: PickupLook,
+    weight: u32,
+    pub amount: u32,
+}
+
+// Looks, spawn weights and strengths of every pickup, read from
+// assets/pickups.ron. Code only decides what each effect does.
+#[derive(Resource, Deserialize)]
+pub struct Pickups {
+    pub food: PickupLook,
+    // modes left out place food uniformly
+    #[serde(default)]
+    food_strategies: Vec<(GameMode, FoodStrategy)>,
+    power_ups: Vec<PowerUpDef>,
+}
+
+impl Pickups {
+    fn load() -> Self {
+        Self::parse(&data_file!("pickups.ron"))
+            .unwrap_or_else(|err| panic!("Broken pickup definitions: {err}"))
+    }
+
+    pub fn parse(contents: &str) -> ron::error::SpannedResult<Self> {
+        ron::from_str(contents)
+    }
+
+    pub fn food_strategy(&self, mode: GameMode) -> FoodStrategy {
+        self.food_strategies
+            .iter()
+            .find(|(m, _)| *m == mode)
+            .map_or_else(FoodStrategy::default, |(_, strategy)| *strategy)
+    }
+
+    pub fn power_ups(&self) -> impl Iterator<Item = &PowerUpDef> {
+        self.power_ups.iter()
+    }
+
+    pub fn power_up(&self, effect: PowerUp) -> Option<&PowerUpDef> {
+        self.power_ups.iter().find(|def| def.effect == effect)
+    }
+
+    pub fn random_power_up(&self, rng: &mut GameRng) -> Option<PowerUp> {
+        let total: u32 = self.power_ups.iter().map(|def| def.weight).sum();
+        if total == 0 {
+            return None;
+        }
+        let mut roll = rng.0.gen_range(0..total);
+        self.power_ups.iter().find_map(|def| {
+            if roll < def.weight {
+                Some(def.effect)
+            } else {
+                roll -= def.weight;
+                None
+            }
+        })
+    }
+}
+
+pub struct PickupsPlugin;
+
+impl Plugin for PickupsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Pickups::load())
+            .add_systems(PostUpdate, dress_pickups.before(size_scaling));
+    }
+}
+
+// Pickups are spawned bare wherever they come from and get their sprite here,
+// and every one is redone when the definitions are reloaded.
+fn dress_pickups(
+    mut commands: Commands,
+    pickups: Res<Pickups>,
+    seasons: Res<Seasons>,
+    progress: Res<Progress>,
+    mut colorblind: Local<bool>,
+    food: Query<(Entity, Ref<Food>, Option<Ref<SeasonalFood>>)>,
+    power_ups: Query<(Entity, Ref<PowerUp>)>,
+) {
+    let all = pickups.is_changed() || seasons.is_changed() || *colorblind != progress.colorblind;
+    *colorblind = progress.colorblind;
+    // seasonal food keeps the plain look if its season has since ended
+    let special = seasons.special_food().map(|special| &special.look);
+    let food = food
+        .iter()
+        .filter(|(_, food, seasonal)| {
+            all || food.is_added() || seasonal.as_ref().is_some_and(|s| s.is_added())
+        })
+        .map(|(ent, _, seasonal)| match (seasonal, special) {
+            (Some(_), Some(look)) => (ent, Some(look)),
+            _ => (ent, Some(&pickups.food)),
+        });
+    let power_ups = power_ups
+        .iter()
+        .filter(|(_, power_up)| all || power_up.is_added())
+        .map(|(ent, power_up)| (ent, pickups.power_up(*power_up).map(|def| &def.look)));
+    let looks = food.chain(power_ups);
+    for (ent, look) in looks {
+        let Some(look) = look else {
+            warn!("No pickup definition for {ent}");
+            continue;
+        };
+        commands.entity(ent).insert((
+            Sprite {
+                color: look.color_for(progress.colorblind),
+                ..default()
+            },
+            Size::square(look.size),
+        ));
+    }
+}
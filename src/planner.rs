@@ -0,0 +1,90 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    time::Instant,
+};
+
+use crate::{Direction, Position};
+
+// A* over the arena grid from `start` to whichever goal is nearest, moving
+// one cell at a time around `blocked`. Returns the cells after `start` up
+// to and including the goal. Gives up with None once `deadline` passes so
+// a caller can keep to a frame budget.
+pub fn route(
+    start: Position,
+    goals: &[Position],
+    blocked: &[Position],
+    deadline: Instant,
+) -> Option<Vec<Position>> {
+    let heuristic = |pos: Position| {
+        goals
+            .iter()
+            .map(|goal| (goal.x - pos.x).abs() + (goal.y - pos.y).abs())
+            .min()
+    };
+
+    let mut open = BinaryHeap::new();
+    let mut came_from: HashMap<Position, Position> = HashMap::new();
+    let mut cost: HashMap<Position, i32> = HashMap::new();
+    open.push(Reverse((heuristic(start)?, start.x, start.y)));
+    cost.insert(start, 0);
+
+    while let Some(Reverse((_, x, y))) = open.pop() {
+        if Instant::now() > deadline {
+            return None;
+        }
+
+        let current = Position { x, y };
+        if goals.contains(&current) {
+            let mut path = vec![current];
+            while let Some(previous) = came_from.get(path.last()?) {
+                if *previous == start {
+                    break;
+                }
+                path.push(*previous);
+            }
+            path.reverse();
+            return Some(path);
+        }
+
+        let next_cost = cost[&current] + 1;
+        for (dx, dy) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+            let next = Position {
+                x: x + dx,
+                y: y + dy,
+            };
+            if !next.in_arena()
+                || blocked.contains(&next)
+                || cost.get(&next).is_some_and(|known| *known <= next_cost)
+            {
+                continue;
+            }
+            cost.insert(next, next_cost);
+            came_from.insert(next, current);
+            open.push(Reverse((next_cost + heuristic(next)?, next.x, next.y)));
+        }
+    }
+    None
+}
+
+// Every cell reachable from `start` without crossing `blocked`, `start`
+// itself included.
+pub fn reachable(start: Position, blocked: &[Position]) -> Vec<Position> {
+    let mut seen = vec![start];
+    let mut next = 0;
+    while let Some(current) = seen.get(next).copied() {
+        next += 1;
+        for direction in [
+            Direction::Left,
+            Direction::Right,
+            Direction::Up,
+            Direction::Down,
+        ] {
+            let neighbour = current.moved(direction);
+            if neighbour.in_arena() && !blocked.contains(&neighbour) && !seen.contains(&neighbour) {
+                seen.push(neighbour);
+            }
+        }
+    }
+    seen
+}
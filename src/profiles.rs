@@ -0,0 +1,151 @@
+use bevy::prelude::*;
+use std::{fs, path::PathBuf};
+
+use crate::{arg_value, locale::Locale, only_menu, progress::Progress, Menu};
+
+const DEFAULT_PROFILE: &str = "default";
+const PROFILE_KEYS: [KeyCode; 9] = [
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+];
+
+pub fn base_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("bevy-snake"))
+}
+
+// Whose progress and saved runs are in use. Picked with `--profile <name>`
+// or from the selector at startup, a new name creates a new profile.
+#[derive(Resource, Clone, PartialEq, Eq)]
+pub struct Profile(String);
+
+impl Profile {
+    pub fn from_args() -> Self {
+        match arg_value("--profile") {
+            Some(name) if Self::valid_name(&name) => Self(name),
+            Some(name) => {
+                warn!("Ignoring invalid profile name {name:?}");
+                Self(DEFAULT_PROFILE.to_string())
+            }
+            None => Self(DEFAULT_PROFILE.to_string()),
+        }
+    }
+
+    // Names end up in paths, so keep them to plain characters.
+    fn valid_name(name: &str) -> bool {
+        !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    }
+
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.0 == DEFAULT_PROFILE
+    }
+
+    // The default profile keeps using the top-level directory so progress
+    // from before profiles existed carries over.
+    pub fn data_file(&self, name: &str) -> Option<PathBuf> {
+        let base = base_dir()?;
+        Some(if self.is_default() {
+            base.join(name)
+        } else {
+            base.join("profiles").join(&self.0).join(name)
+        })
+    }
+
+    fn list() -> Vec<Self> {
+        let mut profiles = vec![Self(DEFAULT_PROFILE.to_string())];
+        let Some(entries) = base_dir().and_then(|dir| fs::read_dir(dir.join("profiles")).ok())
+        else {
+            return profiles;
+        };
+        let mut others: Vec<Self> = entries
+            .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+            .filter(|name| Self::valid_name(name) && name != DEFAULT_PROFILE)
+            .map(Self)
+            .collect();
+        others.sort_by(|a, b| a.0.cmp(&b.0));
+        profiles.extend(others);
+        profiles
+    }
+}
+
+#[derive(Component)]
+pub struct ProfileSelector(Vec<Profile>);
+
+pub struct ProfilesPlugin;
+
+impl Plugin for ProfilesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, offer_profiles)
+            .add_systems(Update, (select_profile, load_profile).chain());
+    }
+}
+
+fn offer_profiles(mut commands: Commands, locale: Res<Locale>) {
+    let profiles = Profile::list();
+    if arg_value("--profile").is_some() || profiles.len() < 2 {
+        return;
+    }
+
+    let mut lines = vec![locale.text("profiles-title").to_string()];
+    for (i, profile) in profiles.iter().take(PROFILE_KEYS.len()).enumerate() {
+        lines.push(format!("{}. {}", i + 1, profile.name()));
+    }
+    lines.push(locale.text("profiles-hint").to_string());
+    commands.spawn((
+        Text::new(lines.join("\n")),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(5.0),
+            left: Val::Px(5.0),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+        ProfileSelector(profiles),
+        Menu,
+    ));
+}
+
+fn select_profile(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut profile: ResMut<Profile>,
+    selector: Query<(Entity, &ProfileSelector)>,
+    menus: Query<Entity, With<Menu>>,
+) {
+    let Ok((ent, ProfileSelector(profiles))) = selector.get_single() else {
+        return;
+    };
+    if !only_menu(ent, &menus) || keyboard_input.get_just_pressed().next().is_none() {
+        return;
+    }
+
+    // any other key keeps the current profile
+    commands.entity(ent).despawn();
+    if let Some(chosen) = PROFILE_KEYS
+        .iter()
+        .zip(profiles)
+        .find(|(key, _)| keyboard_input.just_pressed(**key))
+        .map(|(_, chosen)| chosen)
+    {
+        profile.set_if_neq(chosen.clone());
+    }
+}
+
+fn load_profile(profile: Res<Profile>, mut progress: ResMut<Progress>) {
+    if profile.is_changed() && !profile.is_added() {
+        *progress = Progress::load(&profile);
+    }
+}
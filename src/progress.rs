@@ -0,0 +1,368 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    codex::CodexEntry,
+    controls::Controls,
+    locale::{Language, Locale},
+    missions::Mission,
+    only_menu,
+    profiles::Profile,
+    settings::{ArenaOrientation, DisplaySettings, GraphicsSettings},
+    shop::ArenaTheme,
+    show_toast,
+    telemetry::TelemetrySettings,
+    GameOverEvent, GrowthEvent, Menu, SnakeHead, SnakeSegments, SNAKE_HEAD_COLOR,
+    SNAKE_SEGMENT_COLOR,
+};
+
+const PROGRESS_FILE: &str = "progress.ron";
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Skin {
+    Classic,
+    Neon,
+    Ember,
+    Ghost,
+}
+
+impl Skin {
+    const ALL: [Self; 4] = [Self::Classic, Self::Neon, Self::Ember, Self::Ghost];
+
+    pub fn name(self, locale: &Locale) -> &str {
+        locale.text(match self {
+            Self::Classic => "skin-classic",
+            Self::Neon => "skin-neon",
+            Self::Ember => "skin-ember",
+            Self::Ghost => "skin-ghost",
+        })
+    }
+
+    fn requirement(self, locale: &Locale) -> &str {
+        locale.text(match self {
+            Self::Classic => "skin-classic-requirement",
+            Self::Neon => "skin-neon-requirement",
+            Self::Ember => "skin-ember-requirement",
+            Self::Ghost => "skin-ghost-requirement",
+        })
+    }
+
+    fn earned(self, stats: &Stats) -> bool {
+        match self {
+            Self::Classic => true,
+            Self::Neon => stats.lifetime_food >= 1000,
+            Self::Ember => stats.best_length >= 15,
+            Self::Ghost => stats.runs_played >= 50,
+        }
+    }
+
+    pub fn colors(self) -> (Color, Color) {
+        match self {
+            Self::Classic => (SNAKE_HEAD_COLOR, SNAKE_SEGMENT_COLOR),
+            Self::Neon => (Color::srgb(0.2, 1.0, 0.6), Color::srgb(1.0, 0.2, 0.8)),
+            Self::Ember => (Color::srgb(1.0, 0.5, 0.1), Color::srgb(0.6, 0.15, 0.05)),
+            Self::Ghost => (
+                Color::srgba(0.9, 0.9, 1.0, 0.6),
+                Color::srgba(0.7, 0.7, 0.9, 0.3),
+            ),
+        }
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+struct Stats {
+    lifetime_food: u32,
+    best_length: usize,
+    runs_played: u32,
+    perfect_games: u32,
+}
+
+// Lifetime stats, unlocks and shop purchases, saved between sessions.
+#[derive(Resource, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Progress {
+    stats: Stats,
+    pub unlocked: Vec<Skin>,
+    pub selected: Skin,
+    pub coins: u32,
+    pub themes: Vec<ArenaTheme>,
+    pub theme: ArenaTheme,
+    pub starting_shields: u32,
+    pub missions: Vec<(Mission, u32)>,
+    // codex entries found so far
+    pub codex: Vec<CodexEntry>,
+    pub language: Language,
+    pub ui_scale: f32,
+    pub rumble: f32,
+    // turns off purely decorative movement like the body wiggle, and tones
+    // down afterimages
+    pub reduced_motion: bool,
+    // afterimages behind every move, not only dashes
+    pub afterimages_always: bool,
+    // pickups use their colour-blind friendly palette
+    pub colorblind: bool,
+    // small inset view following the end of player one's tail
+    pub tail_camera: bool,
+    // faint line along the planner's suggested route to the nearest food
+    pub coach: bool,
+    pub controls: Controls,
+    pub display: DisplaySettings,
+    pub orientation: ArenaOrientation,
+    pub graphics: GraphicsSettings,
+    pub telemetry: TelemetrySettings,
+    // Unix time of the last local change, used to settle sync conflicts.
+    pub saved_at: u64,
+    #[serde(skip)]
+    run_food: u32,
+    // No progress file existed yet, so this profile hasn't been set up.
+    #[serde(skip)]
+    pub first_run: bool,
+    // Which profile's file this came from and is saved back to.
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+impl Default for Progress {
+    fn default() -> Self {
+        Self {
+            stats: Stats::default(),
+            unlocked: vec![Skin::Classic],
+            selected: Skin::Classic,
+            coins: 0,
+            themes: vec![ArenaTheme::Default],
+            theme: ArenaTheme::Default,
+            starting_shields: 0,
+            missions: Vec::new(),
+            codex: Vec::new(),
+            language: Language::default(),
+            ui_scale: 1.0,
+            rumble: 1.0,
+            reduced_motion: false,
+            afterimages_always: false,
+            colorblind: false,
+            tail_camera: false,
+            coach: false,
+            controls: Controls::default(),
+            display: DisplaySettings::default(),
+            orientation: ArenaOrientation::default(),
+            graphics: GraphicsSettings::default(),
+            telemetry: TelemetrySettings::default(),
+            saved_at: 0,
+            run_food: 0,
+            first_run: false,
+            path: None,
+        }
+    }
+}
+
+impl Progress {
+    pub fn record_perfect_game(&mut self) {
+        self.stats.perfect_games += 1;
+        self.save();
+    }
+
+    pub fn load(profile: &Profile) -> Self {
+        let path = profile.data_file(PROGRESS_FILE);
+        let mut progress = match path.as_ref().and_then(|path| fs::read_to_string(path).ok()) {
+            Some(contents) => ron::from_str(&contents).unwrap_or_else(|err| {
+                warn!("Ignoring unreadable progress file: {err}");
+                Self::default()
+            }),
+            None => Self {
+                first_run: true,
+                ..Self::default()
+            },
+        };
+        progress.path = path;
+        progress
+    }
+
+    // Takes on progress from elsewhere, still saving to this profile's file.
+    pub fn adopt(&mut self, other: Self) {
+        let path = self.path.take();
+        *self = other;
+        self.path = path;
+    }
+
+    pub fn save(&mut self) {
+        self.saved_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        self.write();
+    }
+
+    pub fn to_ron(&self) -> Result<String, ron::Error> {
+        ron::ser::to_string_pretty(self, default())
+    }
+
+    // Writes to disk as-is, without marking this as a new local change.
+    pub fn write(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let contents = match self.to_ron() {
+            Ok(contents) => contents,
+            Err(err) => {
+                warn!("Failed to serialize progress: {err}");
+                return;
+            }
+        };
+        if let Err(err) = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(path, contents))
+        {
+            warn!("Failed to save progress to {}: {err}", path.display());
+        }
+    }
+}
+
+#[derive(Component)]
+struct Gallery;
+
+pub struct ProgressPlugin;
+
+impl Plugin for ProgressPlugin {
+    fn build(&self, app: &mut App) {
+        let progress = Progress::load(app.world().resource::<Profile>());
+        app.insert_resource(progress).add_systems(
+            Update,
+            (track_progress, toggle_gallery, select_skin, update_gallery).chain(),
+        );
+    }
+}
+
+fn track_progress(
+    mut commands: Commands,
+    locale: Res<Locale>,
+    mut progress: ResMut<Progress>,
+    mut growth_reader: EventReader<GrowthEvent>,
+    mut game_over_reader: EventReader<GameOverEvent>,
+    heads: Query<&SnakeSegments, With<SnakeHead>>,
+) {
+    let eaten = growth_reader.read().count() as u32;
+    let runs = game_over_reader.read().count() as u32;
+    if eaten == 0 && runs == 0 {
+        return;
+    }
+
+    progress.stats.lifetime_food += eaten;
+    progress.stats.runs_played += runs;
+    progress.run_food += eaten;
+
+    // every food eaten during a run is worth a coin once it ends
+    if runs > 0 && progress.run_food > 0 {
+        let earned = progress.run_food;
+        progress.coins += earned;
+        progress.run_food = 0;
+        show_toast(
+            &mut commands,
+            locale.format("toast-coins", &[("coins", &earned)]),
+        );
+    }
+    if let Some(length) = heads.iter().map(|segments| segments.0.len()).max() {
+        progress.stats.best_length = progress.stats.best_length.max(length);
+    }
+
+    let mut unlocked_any = false;
+    for skin in Skin::ALL {
+        if !progress.unlocked.contains(&skin) && skin.earned(&progress.stats) {
+            progress.unlocked.push(skin);
+            show_toast(
+                &mut commands,
+                locale.format("toast-skin-unlocked", &[("skin", &skin.name(&locale))]),
+            );
+            unlocked_any = true;
+        }
+    }
+
+    // stats are only written at the end of a run, unlocks straight away
+    if runs > 0 || unlocked_any {
+        progress.save();
+    }
+}
+
+fn toggle_gallery(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gallery: Query<Entity, With<Gallery>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::KeyG) {
+        return;
+    }
+
+    match gallery.get_single() {
+        Ok(ent) => commands.entity(ent).despawn(),
+        Err(_) => {
+            commands.spawn((
+                Text::default(),
+                Node {
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Px(5.0),
+                    left: Val::Px(5.0),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+                Gallery,
+                Menu,
+            ));
+        }
+    }
+}
+
+fn select_skin(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut progress: ResMut<Progress>,
+    gallery: Query<Entity, With<Gallery>>,
+    menus: Query<Entity, With<Menu>>,
+) {
+    if !gallery.get_single().is_ok_and(|ent| only_menu(ent, &menus)) {
+        return;
+    }
+
+    const KEYS: [KeyCode; 4] = [
+        KeyCode::Digit1,
+        KeyCode::Digit2,
+        KeyCode::Digit3,
+        KeyCode::Digit4,
+    ];
+    for (key, skin) in KEYS.iter().zip(Skin::ALL) {
+        if keyboard_input.just_pressed(*key) && progress.unlocked.contains(&skin) {
+            progress.selected = skin;
+            progress.save();
+        }
+    }
+}
+
+fn update_gallery(
+    locale: Res<Locale>,
+    progress: Res<Progress>,
+    mut gallery: Query<&mut Text, With<Gallery>>,
+) {
+    for mut text in gallery.iter_mut() {
+        let mut lines = vec![locale.text("gallery-title").to_string()];
+        for (i, skin) in Skin::ALL.iter().enumerate() {
+            let state = if *skin == progress.selected {
+                "gallery-worn"
+            } else if progress.unlocked.contains(skin) {
+                "gallery-unlocked"
+            } else {
+                "gallery-locked"
+            };
+            lines.push(format!(
+                "{}. {} [{}] {}",
+                i + 1,
+                skin.name(&locale),
+                locale.text(state),
+                skin.requirement(&locale)
+            ));
+        }
+        text.0 = lines.join("\n");
+    }
+}
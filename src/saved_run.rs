@@ -0,0 +1,564 @@
+use bevy::{
+    prelude::*,
+    window::{PrimaryWindow, WindowCloseRequested},
+};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    arcade::Stage,
+    crash_report,
+    dash::Dash,
+    locale::Locale,
+    objectives::{spawn_numbered_pellet, Numbered},
+    obstacles::{spawn_block, Block},
+    profiles::Profile,
+    score_breakdown::ScoreBreakdown,
+    show_toast, spawn_food, spawn_projectile, spawn_snake_at, spawn_trail, ArenaItems, Direction,
+    FixedTimer, Food, FoodSpawnerTimer, GameMode, GameOverEvent, GameRng, LastTailPosition,
+    Launcher, Lives, Menu, Player, Position, PowerUp, PowerUpSpawnerTimer, Projectile, Score,
+    Shield, SnakeHead, SnakeSegment, SnakeSegments, StatusEffect, StatusEffects, Toast, Trail,
+};
+
+const SAVED_RUN_FILE: &str = "run.ron";
+const AUTOSAVE_SLOTS: usize = 2;
+const AUTOSAVE_INTERVAL_SECS: f32 = 5.0;
+
+#[derive(Clone, Serialize, Deserialize)]
+struct SavedSnake {
+    player: Player,
+    direction: Direction,
+    // head first, same order as SnakeSegments
+    segments: Vec<Position>,
+    last_tail: Option<Position>,
+    shots: u32,
+    cooldown: u32,
+    #[serde(default)]
+    dash_charge: u32,
+    #[serde(default)]
+    dash_cooldown: u32,
+    effects: Vec<(StatusEffect, u32)>,
+    shielded: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct SavedProjectile {
+    direction: Direction,
+    // entities don't survive a restart, the owner is matched up by player
+    owner: Player,
+    ticks: u32,
+    position: Position,
+}
+
+// Everything needed to pick a run back up exactly where it was left.
+// World events aren't included, a resumed run starts without one active.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SavedRun {
+    mode: GameMode,
+    score: u32,
+    lives: u32,
+    #[serde(default)]
+    stage: Stage,
+    #[serde(default)]
+    breakdown: ScoreBreakdown,
+    snakes: Vec<SavedSnake>,
+    food: Vec<Position>,
+    // objective pellets, kept apart so older saves' food still loads
+    #[serde(default)]
+    pellets: Vec<(u32, Position)>,
+    power_ups: Vec<(PowerUp, Position)>,
+    projectiles: Vec<SavedProjectile>,
+    trails: Vec<(Player, Position)>,
+    #[serde(default)]
+    blocks: Vec<Position>,
+    movement_elapsed: Duration,
+    food_spawner_elapsed: Duration,
+    power_up_spawner_elapsed: Duration,
+    rng: ChaCha8Rng,
+}
+
+impl SavedRun {
+    pub fn capture(world: &mut World) -> Self {
+        let mut positions = world.query::<&Position>();
+        let mut snakes = Vec::new();
+        let mut heads = world.query::<(
+            &SnakeHead,
+            &SnakeSegments,
+            &LastTailPosition,
+            &Launcher,
+            &Dash,
+            &StatusEffects,
+            Has<Shield>,
+        )>();
+        for (head, segments, last_tail, launcher, dash, effects, shielded) in heads.iter(world) {
+            snakes.push(SavedSnake {
+                player: head.player,
+                direction: head.direction,
+                segments: segments
+                    .0
+                    .iter()
+                    .filter_map(|ent| positions.get(world, *ent).ok().copied())
+                    .collect(),
+                last_tail: last_tail.0,
+                shots: launcher.shots,
+                cooldown: launcher.cooldown,
+                dash_charge: dash.charge,
+                dash_cooldown: dash.cooldown,
+                effects: effects.0.clone(),
+                shielded,
+            });
+        }
+
+        let mut owners = world.query::<&SnakeHead>();
+        let projectiles = world
+            .query::<(&Projectile, &Position)>()
+            .iter(world)
+            .filter_map(|(projectile, position)| {
+                Some(SavedProjectile {
+                    direction: projectile.direction,
+                    owner: owners.get(world, projectile.owner).ok()?.player,
+                    ticks: projectile.ticks,
+                    position: *position,
+                })
+            })
+            .collect();
+
+        Self {
+            mode: *world.resource::<GameMode>(),
+            score: world.resource::<Score>().0,
+            lives: world.resource::<Lives>().0,
+            stage: world.resource::<Stage>().clone(),
+            breakdown: world.resource::<ScoreBreakdown>().clone(),
+            snakes,
+            food: world
+                .query_filtered::<&Position, (With<Food>, Without<Numbered>)>()
+                .iter(world)
+                .copied()
+                .collect(),
+            pellets: world
+                .query_filtered::<(&Numbered, &Position), With<Food>>()
+                .iter(world)
+                .map(|(number, position)| (number.0, *position))
+                .collect(),
+            power_ups: world
+                .query::<(&PowerUp, &Position)>()
+                .iter(world)
+                .map(|(power_up, position)| (*power_up, *position))
+                .collect(),
+            projectiles,
+            trails: world
+                .query::<(&Trail, &Position)>()
+                .iter(world)
+                .map(|(trail, position)| (trail.0, *position))
+                .collect(),
+            blocks: world
+                .query_filtered::<&Position, With<Block>>()
+                .iter(world)
+                .copied()
+                .collect(),
+            movement_elapsed: world.resource::<FixedTimer>().0.elapsed(),
+            food_spawner_elapsed: world.resource::<FoodSpawnerTimer>().0.elapsed(),
+            power_up_spawner_elapsed: world.resource::<PowerUpSpawnerTimer>().0.elapsed(),
+            rng: world.resource::<GameRng>().0.clone(),
+        }
+    }
+
+    pub fn restore(self, world: &mut World) {
+        let stale: Vec<Entity> = world
+            .query_filtered::<Entity, Or<(ArenaItems, With<SnakeSegment>)>>()
+            .iter(world)
+            .collect();
+        for ent in stale {
+            world.despawn(ent);
+        }
+
+        world.insert_resource(self.mode);
+        world.insert_resource(Score(self.score));
+        world.insert_resource(Lives(self.lives));
+        world.insert_resource(self.stage);
+        world.insert_resource(self.breakdown);
+        world.insert_resource(GameRng(self.rng));
+        world
+            .resource_mut::<FixedTimer>()
+            .0
+            .set_elapsed(self.movement_elapsed);
+        world
+            .resource_mut::<FoodSpawnerTimer>()
+            .0
+            .set_elapsed(self.food_spawner_elapsed);
+        world
+            .resource_mut::<PowerUpSpawnerTimer>()
+            .0
+            .set_elapsed(self.power_up_spawner_elapsed);
+
+        let mut commands = world.commands();
+        let mut owners = Vec::new();
+        for snake in self.snakes {
+            let head = spawn_snake_at(
+                &mut commands,
+                snake.player,
+                snake.direction,
+                &snake.segments,
+            );
+            let mut head_commands = commands.entity(head);
+            head_commands.insert((
+                LastTailPosition(snake.last_tail),
+                Launcher {
+                    shots: snake.shots,
+                    cooldown: snake.cooldown,
+                },
+                Dash::restored(snake.dash_charge, snake.dash_cooldown),
+                StatusEffects(snake.effects),
+            ));
+            if snake.shielded {
+                head_commands.insert(Shield);
+            }
+            owners.push((snake.player, head));
+        }
+        for position in self.food {
+            spawn_food(&mut commands, position);
+        }
+        for (number, position) in self.pellets {
+            spawn_numbered_pellet(&mut commands, position, number);
+        }
+        for (power_up, position) in self.power_ups {
+            commands.spawn((power_up, position));
+        }
+        for projectile in self.projectiles {
+            let Some((_, owner)) = owners
+                .iter()
+                .find(|(player, _)| *player == projectile.owner)
+            else {
+                continue;
+            };
+            spawn_projectile(
+                &mut commands,
+                Projectile {
+                    direction: projectile.direction,
+                    owner: *owner,
+                    ticks: projectile.ticks,
+                },
+                projectile.position,
+            );
+        }
+        for (player, position) in self.trails {
+            spawn_trail(&mut commands, player, position);
+        }
+        for position in self.blocks {
+            spawn_block(&mut commands, position);
+        }
+        world.flush();
+    }
+
+    fn load(profile: &Profile, name: &str) -> Option<Self> {
+        let contents = fs::read_to_string(profile.data_file(name)?).ok()?;
+        ron::from_str(&contents)
+            .inspect_err(|err| warn!("Ignoring unreadable saved run {name}: {err}"))
+            .ok()
+    }
+
+    fn write(&self, profile: &Profile, name: &str) -> bool {
+        let Some(path) = profile.data_file(name) else {
+            return false;
+        };
+        let contents = match ron::ser::to_string_pretty(self, default()) {
+            Ok(contents) => contents,
+            Err(err) => {
+                warn!("Failed to serialize run: {err}");
+                return false;
+            }
+        };
+        if let Err(err) = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(&path, contents))
+        {
+            warn!("Failed to save run to {}: {err}", path.display());
+            return false;
+        }
+        true
+    }
+}
+
+fn autosave_file(slot: usize) -> String {
+    format!("autosave-{slot}.ron")
+}
+
+// Autosaves left behind by a session that didn't quit cleanly, newest first.
+fn leftover_autosaves(profile: &Profile) -> Vec<String> {
+    let mut autosaves: Vec<(String, SystemTime)> = (0..AUTOSAVE_SLOTS)
+        .map(autosave_file)
+        .filter_map(|name| {
+            let modified = fs::metadata(profile.data_file(&name)?)
+                .ok()?
+                .modified()
+                .ok()?;
+            Some((name, modified))
+        })
+        .collect();
+    autosaves.sort_by_key(|(_, modified)| std::cmp::Reverse(*modified));
+    autosaves.into_iter().map(|(name, _)| name).collect()
+}
+
+fn delete_autosaves(profile: &Profile) {
+    for slot in 0..AUTOSAVE_SLOTS {
+        if let Some(path) = profile.data_file(&autosave_file(slot)) {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+fn delete_saves(profile: &Profile) {
+    if let Some(path) = profile.data_file(SAVED_RUN_FILE) {
+        let _ = fs::remove_file(path);
+    }
+    delete_autosaves(profile);
+}
+
+// Stays on screen until the player either continues or starts playing.
+// Holds the files to try restoring from, in order.
+#[derive(Component)]
+struct ContinuePrompt(Vec<String>);
+
+// Shown instead of closing the window straight away, the run is paused meanwhile.
+#[derive(Component)]
+struct QuitDialog;
+
+#[derive(Resource)]
+struct QuitWithoutSaving(bool);
+
+// Slots are written in turn so a crash halfway through a write still
+// leaves the previous snapshot intact.
+#[derive(Resource)]
+struct Autosave {
+    timer: Timer,
+    next_slot: usize,
+}
+
+pub struct SavedRunPlugin;
+
+impl Plugin for SavedRunPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Autosave {
+            timer: Timer::from_seconds(AUTOSAVE_INTERVAL_SECS, TimerMode::Repeating),
+            next_slot: 0,
+        })
+        .insert_resource(QuitWithoutSaving(false))
+        .add_systems(
+            Update,
+            (
+                offer_continue,
+                continue_run,
+                save_run_on_key,
+                autosave,
+                snapshot_for_crash,
+                discard_saved_run,
+                request_quit,
+                confirm_quit,
+            )
+                .chain(),
+        )
+        .add_systems(Last, save_run_on_exit);
+    }
+}
+
+// Runs at launch and again whenever another profile is picked.
+fn offer_continue(
+    mut commands: Commands,
+    locale: Res<Locale>,
+    profile: Res<Profile>,
+    prompts: Query<Entity, With<ContinuePrompt>>,
+) {
+    if !profile.is_changed() {
+        return;
+    }
+    for ent in prompts.iter() {
+        commands.entity(ent).despawn();
+    }
+
+    let (key, files) = if profile
+        .data_file(SAVED_RUN_FILE)
+        .is_some_and(|path| path.exists())
+    {
+        ("run-continue", vec![SAVED_RUN_FILE.to_string()])
+    } else {
+        let autosaves = leftover_autosaves(&profile);
+        if autosaves.is_empty() {
+            return;
+        }
+        ("run-recover", autosaves)
+    };
+
+    let prompt = show_toast(&mut commands, locale.text(key));
+    commands
+        .entity(prompt)
+        .remove::<Toast>()
+        .insert((ContinuePrompt(files), Menu));
+}
+
+fn continue_run(world: &mut World) {
+    let mut prompts = world.query::<(Entity, &ContinuePrompt)>();
+    let Some((prompt, ContinuePrompt(files))) = prompts.iter(world).next() else {
+        return;
+    };
+    let keyboard_input = world.resource::<ButtonInput<KeyCode>>();
+    if keyboard_input.get_just_pressed().next().is_none() {
+        return;
+    }
+    let continuing = keyboard_input.just_pressed(KeyCode::KeyC);
+    let files = files.clone();
+    world.despawn(prompt);
+
+    if !continuing {
+        return;
+    }
+    let profile = world.resource::<Profile>().clone();
+    match files.iter().find_map(|name| SavedRun::load(&profile, name)) {
+        Some(run) => {
+            run.restore(world);
+            delete_saves(&profile);
+        }
+        None => {
+            let message = world
+                .resource::<Locale>()
+                .text("run-load-failed")
+                .to_string();
+            show_toast(&mut world.commands(), message);
+            world.flush();
+        }
+    }
+}
+
+fn save_run_on_key(world: &mut World) {
+    if !world
+        .resource::<ButtonInput<KeyCode>>()
+        .just_pressed(KeyCode::F2)
+    {
+        return;
+    }
+    let profile = world.resource::<Profile>().clone();
+    if SavedRun::capture(world).write(&profile, SAVED_RUN_FILE) {
+        let message = world.resource::<Locale>().text("run-saved").to_string();
+        show_toast(&mut world.commands(), message);
+        world.flush();
+    }
+}
+
+fn autosave(world: &mut World) {
+    // the last session's autosaves are left alone until the player has chosen
+    let mut prompts = world.query_filtered::<(), With<ContinuePrompt>>();
+    if prompts.iter(world).next().is_some() {
+        return;
+    }
+
+    let delta = world.resource::<Time>().delta();
+    let mut autosave = world.resource_mut::<Autosave>();
+    if !autosave.timer.tick(delta).just_finished() {
+        return;
+    }
+    let slot = autosave.next_slot;
+    autosave.next_slot = (slot + 1) % AUTOSAVE_SLOTS;
+    let profile = world.resource::<Profile>().clone();
+    SavedRun::capture(world).write(&profile, &autosave_file(slot));
+}
+
+// Keeps an in-memory copy of the run every tick for the panic hook to write
+// out, fresher than the last autosave.
+fn snapshot_for_crash(world: &mut World) {
+    if !world.resource::<FixedTimer>().0.just_finished() {
+        return;
+    }
+    let mut prompts = world.query_filtered::<(), With<ContinuePrompt>>();
+    if prompts.iter(world).next().is_some() {
+        return;
+    }
+
+    let slot = world.resource::<Autosave>().next_slot;
+    let Some(path) = world.resource::<Profile>().data_file(&autosave_file(slot)) else {
+        return;
+    };
+    match ron::ser::to_string_pretty(&SavedRun::capture(world), default()) {
+        Ok(contents) => crash_report::stash_run(path, contents),
+        Err(err) => warn!("Failed to serialize run: {err}"),
+    }
+}
+
+// Closing the main window asks first, a second close request quits with a
+// save. Any other window just closes.
+fn request_quit(
+    mut commands: Commands,
+    locale: Res<Locale>,
+    mut time: ResMut<Time<Virtual>>,
+    mut close_reader: EventReader<WindowCloseRequested>,
+    primary: Single<Entity, With<PrimaryWindow>>,
+    dialog: Query<(), With<QuitDialog>>,
+    mut exit_writer: EventWriter<AppExit>,
+) {
+    if !close_reader.read().any(|event| event.window == *primary) {
+        return;
+    }
+    if !dialog.is_empty() {
+        exit_writer.send(AppExit::Success);
+        return;
+    }
+
+    let dialog = show_toast(&mut commands, locale.text("quit-confirm"));
+    commands
+        .entity(dialog)
+        .remove::<Toast>()
+        .insert((QuitDialog, Menu));
+    time.pause();
+}
+
+fn confirm_quit(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    profile: Res<Profile>,
+    mut time: ResMut<Time<Virtual>>,
+    mut without_saving: ResMut<QuitWithoutSaving>,
+    dialog: Query<Entity, With<QuitDialog>>,
+    mut exit_writer: EventWriter<AppExit>,
+) {
+    let Ok(dialog) = dialog.get_single() else {
+        return;
+    };
+
+    if keyboard_input.just_pressed(KeyCode::Enter) {
+        exit_writer.send(AppExit::Success);
+    } else if keyboard_input.just_pressed(KeyCode::KeyQ) {
+        delete_saves(&profile);
+        without_saving.0 = true;
+        exit_writer.send(AppExit::Success);
+    } else if keyboard_input.just_pressed(KeyCode::Escape) {
+        commands.entity(dialog).despawn();
+        time.unpause();
+    }
+}
+
+fn save_run_on_exit(world: &mut World) {
+    if world.resource::<Events<AppExit>>().is_empty() || world.resource::<QuitWithoutSaving>().0 {
+        return;
+    }
+    // quitting with the last run still on offer keeps it, rather than saving
+    // over it with the one barely started underneath
+    let mut prompts = world.query_filtered::<(), With<ContinuePrompt>>();
+    if prompts.iter(world).next().is_some() {
+        return;
+    }
+    // autosaves are only there for crashes, a clean exit has its own save
+    let profile = world.resource::<Profile>().clone();
+    if SavedRun::capture(world).write(&profile, SAVED_RUN_FILE) {
+        delete_autosaves(&profile);
+    }
+}
+
+// A finished run can't be continued.
+fn discard_saved_run(profile: Res<Profile>, mut game_over_reader: EventReader<GameOverEvent>) {
+    if game_over_reader.read().next().is_some() {
+        delete_saves(&profile);
+    }
+}
@@ -0,0 +1,165 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{locale::Locale, GameOverEvent, Score, ScoreEvent, ScoreSource};
+
+const RESULTS_SECS: f32 = 5.0;
+const BAR_HEIGHT: f32 = 16.0;
+
+impl ScoreSource {
+    const ALL: [Self; 4] = [
+        Self::Food,
+        Self::OrderBonus,
+        Self::MutationBonus,
+        Self::SeasonalBonus,
+    ];
+
+    fn name(self, locale: &Locale) -> &str {
+        locale.text(match self {
+            Self::Food => "score-source-food",
+            Self::OrderBonus => "score-source-order-bonus",
+            Self::MutationBonus => "score-source-mutation-bonus",
+            Self::SeasonalBonus => "score-source-seasonal-bonus",
+        })
+    }
+
+    fn color(self) -> Color {
+        match self {
+            Self::Food => Color::srgb(1.0, 0.0, 1.0),
+            Self::OrderBonus => Color::srgb(0.3, 0.7, 1.0),
+            Self::MutationBonus => Color::srgb(1.0, 0.85, 0.2),
+            Self::SeasonalBonus => Color::srgb(1.0, 0.5, 0.0),
+        }
+    }
+}
+
+// Points this run has scored from each source, kept with saved runs so a
+// resumed run's results still add up.
+#[derive(Resource, Clone, Default, Serialize, Deserialize)]
+pub struct ScoreBreakdown(Vec<(ScoreSource, u32)>);
+
+impl ScoreBreakdown {
+    fn add(&mut self, source: ScoreSource, points: u32) {
+        match self.0.iter_mut().find(|(known, _)| *known == source) {
+            Some((_, total)) => *total += points,
+            None => self.0.push((source, points)),
+        }
+    }
+
+    fn points(&self, source: ScoreSource) -> u32 {
+        self.0
+            .iter()
+            .find(|(known, _)| *known == source)
+            .map_or(0, |(_, points)| *points)
+    }
+}
+
+// How the last run's score was made up, shown over the start of the next
+// and timed in real time so a pause doesn't keep it up.
+#[derive(Component)]
+struct Results(Timer);
+
+pub struct ScoreBreakdownPlugin;
+
+impl Plugin for ScoreBreakdownPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ScoreBreakdown>()
+            .add_systems(Update, (show_results, expire_results));
+    }
+}
+
+// The only place the score goes up, so every point has a source.
+pub fn tally_score(
+    mut score: ResMut<Score>,
+    mut breakdown: ResMut<ScoreBreakdown>,
+    mut score_reader: EventReader<ScoreEvent>,
+) {
+    for ScoreEvent { source, points } in score_reader.read() {
+        score.0 += points;
+        breakdown.add(*source, *points);
+    }
+}
+
+fn show_results(
+    mut commands: Commands,
+    locale: Res<Locale>,
+    mut breakdown: ResMut<ScoreBreakdown>,
+    mut game_over_reader: EventReader<GameOverEvent>,
+    results: Query<Entity, With<Results>>,
+) {
+    if game_over_reader.read().next().is_none() {
+        return;
+    }
+    let breakdown = std::mem::take(&mut *breakdown);
+    let total: u32 = breakdown.0.iter().map(|(_, points)| points).sum();
+    if total == 0 {
+        return;
+    }
+
+    for ent in results.iter() {
+        commands.entity(ent).despawn_recursive();
+    }
+    let sources: Vec<(ScoreSource, u32)> = ScoreSource::ALL
+        .into_iter()
+        .map(|source| (source, breakdown.points(source)))
+        .filter(|(_, points)| *points > 0)
+        .collect();
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Percent(15.0),
+                left: Val::Percent(20.0),
+                width: Val::Percent(60.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(4.0),
+                padding: UiRect::all(Val::Px(8.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+            Results(Timer::from_seconds(RESULTS_SECS, TimerMode::Once)),
+        ))
+        .with_children(|panel| {
+            panel.spawn(Text::new(
+                locale.format("results-score", &[("score", &total)]),
+            ));
+            // one bar, each source's share of it in its own colour
+            panel
+                .spawn(Node {
+                    width: Val::Percent(100.0),
+                    height: Val::Px(BAR_HEIGHT),
+                    ..default()
+                })
+                .with_children(|bar| {
+                    for (source, points) in &sources {
+                        bar.spawn((
+                            Node {
+                                width: Val::Percent(*points as f32 / total as f32 * 100.0),
+                                height: Val::Percent(100.0),
+                                ..default()
+                            },
+                            BackgroundColor(source.color()),
+                        ));
+                    }
+                });
+            for (source, points) in &sources {
+                panel.spawn((
+                    Text::new(format!("{}: {points}", source.name(&locale))),
+                    TextColor(source.color()),
+                ));
+            }
+        });
+}
+
+fn expire_results(
+    mut commands: Commands,
+    real_time: Res<Time<Real>>,
+    mut results: Query<(Entity, &mut Results)>,
+) {
+    for (ent, mut results) in results.iter_mut() {
+        if results.0.tick(real_time.delta()).finished() {
+            commands.entity(ent).despawn_recursive();
+        }
+    }
+}
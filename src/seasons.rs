@@ -0,0 +1,164 @@
+use bevy::prelude::*;
+use rand::Rng;
+use serde::Deserialize;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{
+    arg_value, hot_reload::data_file, locale::Locale, pickups::PickupLook, show_toast,
+    snake_eating, snake_movement, Food, GameRng, Position, ScoreEvent, ScoreSource, SnakeHead,
+};
+
+#[derive(Deserialize)]
+pub struct SpecialFood {
+    pub look: PickupLook,
+    chance: f64,
+    points: u32,
+}
+
+#[derive(Deserialize)]
+struct Season {
+    name: String,
+    start: (u32, u32),
+    end: (u32, u32),
+    clear_color: Option<(f32, f32, f32)>,
+    special_food: Option<SpecialFood>,
+}
+
+impl Season {
+    fn covers(&self, date: (u32, u32)) -> bool {
+        if self.start <= self.end {
+            self.start <= date && date <= self.end
+        } else {
+            date >= self.start || date <= self.end
+        }
+    }
+}
+
+// Dated content from assets/seasons.ron, with whichever event covers
+// today picked out.
+#[derive(Resource)]
+pub struct Seasons {
+    seasons: Vec<Season>,
+    // (month, day), `--date 10-31` stands in for the clock when testing
+    today: (u32, u32),
+}
+
+impl Seasons {
+    fn load() -> Self {
+        Self::parse(&data_file!("seasons.ron"))
+            .unwrap_or_else(|err| panic!("Broken seasonal events: {err}"))
+    }
+
+    pub fn parse(contents: &str) -> ron::error::SpannedResult<Self> {
+        let today = arg_value("--date")
+            .and_then(|date| {
+                let (month, day) = date.split_once('-')?;
+                Some((month.parse().ok()?, day.parse().ok()?))
+            })
+            .unwrap_or_else(today);
+        Ok(Self {
+            seasons: ron::from_str(contents)?,
+            today,
+        })
+    }
+
+    fn active(&self) -> Option<&Season> {
+        self.seasons.iter().find(|season| season.covers(self.today))
+    }
+
+    pub fn clear_color(&self) -> Option<Color> {
+        let (red, green, blue) = self.active()?.clear_color?;
+        Some(Color::srgb(red, green, blue))
+    }
+
+    pub fn special_food(&self) -> Option<&SpecialFood> {
+        self.active()?.special_food.as_ref()
+    }
+}
+
+// Today's month and day in UTC, from the days since the Unix epoch.
+fn today() -> (u32, u32) {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    // Howard Hinnant's civil_from_days, shifted so years start in March
+    let days = (secs / 86_400) as i64 + 719_468;
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    (month as u32, day as u32)
+}
+
+// Food that turned out to be the season's special kind.
+#[derive(Component)]
+pub struct SeasonalFood;
+
+pub struct SeasonsPlugin;
+
+impl Plugin for SeasonsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Seasons::load())
+            .add_systems(Startup, announce_season)
+            .add_systems(
+                Update,
+                (
+                    swap_in_special_food,
+                    score_special_food
+                        .after(snake_movement)
+                        .before(snake_eating),
+                ),
+            );
+    }
+}
+
+fn announce_season(mut commands: Commands, locale: Res<Locale>, seasons: Res<Seasons>) {
+    if let Some(season) = seasons.active() {
+        show_toast(&mut commands, locale.text(&season.name));
+    }
+}
+
+fn swap_in_special_food(
+    mut commands: Commands,
+    seasons: Res<Seasons>,
+    mut rng: ResMut<GameRng>,
+    food: Query<Entity, Added<Food>>,
+) {
+    let Some(special) = seasons.special_food() else {
+        return;
+    };
+
+    for ent in food.iter() {
+        if rng.0.gen_bool(special.chance) {
+            commands.entity(ent).insert(SeasonalFood);
+        }
+    }
+}
+
+// Runs before the food is eaten, which only scores the base point.
+fn score_special_food(
+    seasons: Res<Seasons>,
+    mut score_writer: EventWriter<ScoreEvent>,
+    food: Query<&Position, (With<Food>, With<SeasonalFood>)>,
+    heads: Query<&Position, With<SnakeHead>>,
+) {
+    let Some(special) = seasons.special_food() else {
+        return;
+    };
+
+    for pos in food.iter() {
+        if heads.iter().any(|head_pos| head_pos == pos) && special.points > 1 {
+            score_writer.send(ScoreEvent {
+                source: ScoreSource::SeasonalBonus,
+                points: special.points - 1,
+            });
+        }
+    }
+}
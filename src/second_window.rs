@@ -0,0 +1,120 @@
+use bevy::{
+    prelude::*,
+    render::camera::{RenderTarget, ScalingMode},
+    window::{MonitorSelection, PrimaryWindow, WindowCloseRequested, WindowPosition, WindowRef},
+};
+
+use crate::{locale::Locale, Hud};
+
+// A second OS window showing the same arena, for a versus opponent or an
+// audience on another monitor. Opened with `--second-window`. It only
+// watches: the run is still simulated once and laid out against the main
+// window, this one scales that layout to fit.
+#[derive(Component)]
+struct SecondWindow;
+
+#[derive(Component)]
+struct SecondWindowCamera;
+
+// The HUD copied into the second window, since UI only draws on one camera.
+#[derive(Component)]
+struct MirroredHud;
+
+pub struct SecondWindowPlugin;
+
+impl Plugin for SecondWindowPlugin {
+    fn build(&self, app: &mut App) {
+        if !std::env::args().any(|arg| arg == "--second-window") {
+            return;
+        }
+
+        app.add_systems(Startup, open_window).add_systems(
+            Update,
+            (fit_arena, mirror_hud, close_window, retitle_window),
+        );
+    }
+}
+
+fn open_window(mut commands: Commands, locale: Res<Locale>) {
+    let window = commands
+        .spawn((
+            Window {
+                title: locale.text("second-window-title").to_string(),
+                resolution: (500.0, 500.0).into(),
+                // the next monitor along, or beside the main window without one
+                position: WindowPosition::Centered(MonitorSelection::Index(1)),
+                ..default()
+            },
+            SecondWindow,
+        ))
+        .id();
+    let camera = commands
+        .spawn((
+            Camera2d,
+            Camera {
+                target: RenderTarget::Window(WindowRef::Entity(window)),
+                ..default()
+            },
+            SecondWindowCamera,
+        ))
+        .id();
+    commands.spawn((
+        Text::default(),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(5.0),
+            left: Val::Px(5.0),
+            ..default()
+        },
+        TargetCamera(camera),
+        MirroredHud,
+    ));
+}
+
+// Everything is placed in the main window's pixels, so the camera here
+// shows that much of the world at whatever size this window is.
+fn fit_arena(
+    primary: Single<&Window, (With<PrimaryWindow>, Changed<Window>)>,
+    mut projection: Single<&mut OrthographicProjection, With<SecondWindowCamera>>,
+) {
+    projection.scaling_mode = ScalingMode::AutoMin {
+        min_width: primary.width(),
+        min_height: primary.height(),
+    };
+}
+
+fn mirror_hud(
+    hud: Single<&Text, (With<Hud>, Changed<Text>)>,
+    mut mirrored: Single<&mut Text, (With<MirroredHud>, Without<Hud>)>,
+) {
+    mirrored.0.clone_from(&hud.0);
+}
+
+// Closing it leaves the game running in the main window.
+fn close_window(
+    mut commands: Commands,
+    mut close_reader: EventReader<WindowCloseRequested>,
+    windows: Query<(), With<SecondWindow>>,
+    cameras: Query<Entity, With<SecondWindowCamera>>,
+    hud: Query<Entity, With<MirroredHud>>,
+) {
+    for event in close_reader.read() {
+        if !windows.contains(event.window) {
+            continue;
+        }
+        commands.entity(event.window).despawn();
+        for ent in cameras.iter().chain(hud.iter()) {
+            commands.entity(ent).despawn();
+        }
+    }
+}
+
+fn retitle_window(locale: Res<Locale>, mut windows: Query<&mut Window, With<SecondWindow>>) {
+    if !locale.is_changed() {
+        return;
+    }
+
+    for mut window in windows.iter_mut() {
+        window.title = locale.text("second-window-title").to_string();
+    }
+}
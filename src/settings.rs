@@ -0,0 +1,486 @@
+use bevy::{
+    prelude::*,
+    window::{Monitor, PresentMode, PrimaryWindow, WindowMode, WindowMoved, WindowResized},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    locale::Locale, progress::Progress, show_toast, Direction, Position, ARENA_HEIGHT, ARENA_WIDTH,
+};
+
+const UI_SCALE_MIN: f32 = 0.75;
+const UI_SCALE_MAX: f32 = 2.0;
+const UI_SCALE_STEP: f32 = 0.25;
+const RUMBLE_STEP: f32 = 0.25;
+const FPS_CAPS: [Option<u32>; 4] = [None, Some(30), Some(60), Some(120)];
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                (adjust_ui_scale, apply_ui_scale).chain(),
+                adjust_rumble,
+                toggle_reduced_motion,
+                toggle_tail_camera,
+                toggle_coach,
+                cycle_orientation,
+                (cycle_display, remember_window, apply_display).chain(),
+                (cycle_graphics, apply_present_mode).chain(),
+            ),
+        )
+        .add_systems(Last, limit_frame_rate);
+    }
+}
+
+// Minus and equals step the scale of every UI element, HUD and panels alike.
+fn adjust_ui_scale(
+    mut commands: Commands,
+    locale: Res<Locale>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut progress: ResMut<Progress>,
+) {
+    let step = if keyboard_input.just_pressed(KeyCode::Equal) {
+        UI_SCALE_STEP
+    } else if keyboard_input.just_pressed(KeyCode::Minus) {
+        -UI_SCALE_STEP
+    } else {
+        return;
+    };
+
+    let scale = (progress.ui_scale + step).clamp(UI_SCALE_MIN, UI_SCALE_MAX);
+    if scale != progress.ui_scale {
+        progress.ui_scale = scale;
+        progress.save();
+    }
+    show_toast(
+        &mut commands,
+        locale.format("toast-ui-scale", &[("percent", &((scale * 100.0) as u32))]),
+    );
+}
+
+fn apply_ui_scale(progress: Res<Progress>, mut ui_scale: ResMut<UiScale>) {
+    if progress.is_changed() {
+        ui_scale.0 = progress.ui_scale.clamp(UI_SCALE_MIN, UI_SCALE_MAX);
+    }
+}
+
+// The bracket keys step gamepad rumble strength, all the way down turns it off.
+fn adjust_rumble(
+    mut commands: Commands,
+    locale: Res<Locale>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut progress: ResMut<Progress>,
+) {
+    let step = if keyboard_input.just_pressed(KeyCode::BracketRight) {
+        RUMBLE_STEP
+    } else if keyboard_input.just_pressed(KeyCode::BracketLeft) {
+        -RUMBLE_STEP
+    } else {
+        return;
+    };
+
+    let rumble = (progress.rumble + step).clamp(0.0, 1.0);
+    if rumble != progress.rumble {
+        progress.rumble = rumble;
+        progress.save();
+    }
+    let message = if rumble == 0.0 {
+        locale.text("toast-rumble-off").to_string()
+    } else {
+        locale.format("toast-rumble", &[("percent", &((rumble * 100.0) as u32))])
+    };
+    show_toast(&mut commands, message);
+}
+
+// F7 switches reduced motion on and off, Ctrl+F7 whether afterimages
+// follow every move or only dashes.
+fn toggle_reduced_motion(
+    mut commands: Commands,
+    locale: Res<Locale>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut progress: ResMut<Progress>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::F7) {
+        return;
+    }
+
+    let key = if keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]) {
+        progress.afterimages_always = !progress.afterimages_always;
+        if progress.afterimages_always {
+            "toast-afterimages-always"
+        } else {
+            "toast-afterimages-dash"
+        }
+    } else {
+        progress.reduced_motion = !progress.reduced_motion;
+        if progress.reduced_motion {
+            "toast-reduced-motion-on"
+        } else {
+            "toast-reduced-motion-off"
+        }
+    };
+    progress.save();
+    show_toast(&mut commands, locale.text(key));
+}
+
+// F3 switches the tail camera inset on and off.
+fn toggle_tail_camera(
+    mut commands: Commands,
+    locale: Res<Locale>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut progress: ResMut<Progress>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::F3) {
+        return;
+    }
+
+    progress.tail_camera = !progress.tail_camera;
+    progress.save();
+    let key = if progress.tail_camera {
+        "toast-tail-camera-on"
+    } else {
+        "toast-tail-camera-off"
+    };
+    show_toast(&mut commands, locale.text(key));
+}
+
+// F4 switches the coach's route overlay on and off.
+fn toggle_coach(
+    mut commands: Commands,
+    locale: Res<Locale>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut progress: ResMut<Progress>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::F4) {
+        return;
+    }
+
+    progress.coach = !progress.coach;
+    progress.save();
+    let key = if progress.coach {
+        "toast-coach-on"
+    } else {
+        "toast-coach-off"
+    };
+    show_toast(&mut commands, locale.text(key));
+}
+
+// How the arena is turned on screen, for vertical monitors and cabinets.
+// Only where things are drawn and which way the keys point change, the
+// game itself always plays out the same way up.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ArenaOrientation {
+    #[default]
+    Upright,
+    // a quarter turn clockwise
+    Rotated90,
+    Rotated180,
+    // left and right swapped
+    Mirrored,
+}
+
+impl ArenaOrientation {
+    fn next(self) -> Self {
+        match self {
+            Self::Upright => Self::Rotated90,
+            Self::Rotated90 => Self::Rotated180,
+            Self::Rotated180 => Self::Mirrored,
+            Self::Mirrored => Self::Upright,
+        }
+    }
+
+    fn key(self) -> &'static str {
+        match self {
+            Self::Upright => "orientation-upright",
+            Self::Rotated90 => "orientation-rotated-90",
+            Self::Rotated180 => "orientation-rotated-180",
+            Self::Mirrored => "orientation-mirrored",
+        }
+    }
+
+    // The cell `pos` is drawn in. The quarter turn relies on the arena
+    // being square.
+    pub fn place(self, pos: Position) -> Position {
+        let right = ARENA_WIDTH as i32 - 1;
+        let top = ARENA_HEIGHT as i32 - 1;
+        match self {
+            Self::Upright => pos,
+            Self::Rotated90 => Position {
+                x: pos.y,
+                y: right - pos.x,
+            },
+            Self::Rotated180 => Position {
+                x: right - pos.x,
+                y: top - pos.y,
+            },
+            Self::Mirrored => Position {
+                x: right - pos.x,
+                y: pos.y,
+            },
+        }
+    }
+
+    // The direction in the arena that a press towards `pressed` on screen
+    // means.
+    pub fn steer(self, pressed: Direction) -> Direction {
+        match (self, pressed) {
+            (Self::Upright, _) => pressed,
+            (Self::Rotated90, Direction::Up) => Direction::Left,
+            (Self::Rotated90, Direction::Right) => Direction::Up,
+            (Self::Rotated90, Direction::Down) => Direction::Right,
+            (Self::Rotated90, Direction::Left) => Direction::Down,
+            (Self::Rotated180, _) => pressed.opposite(),
+            (Self::Mirrored, Direction::Left | Direction::Right) => pressed.opposite(),
+            (Self::Mirrored, _) => pressed,
+        }
+    }
+}
+
+// F12 steps the arena through its orientations.
+fn cycle_orientation(
+    mut commands: Commands,
+    locale: Res<Locale>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut progress: ResMut<Progress>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::F12) {
+        return;
+    }
+
+    progress.orientation = progress.orientation.next();
+    progress.save();
+    show_toast(&mut commands, locale.text(progress.orientation.key()));
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DisplayMode {
+    #[default]
+    Windowed,
+    Borderless,
+    Fullscreen,
+}
+
+impl DisplayMode {
+    fn next(self) -> Self {
+        match self {
+            Self::Windowed => Self::Borderless,
+            Self::Borderless => Self::Fullscreen,
+            Self::Fullscreen => Self::Windowed,
+        }
+    }
+
+    fn key(self) -> &'static str {
+        match self {
+            Self::Windowed => "display-windowed",
+            Self::Borderless => "display-borderless",
+            Self::Fullscreen => "display-fullscreen",
+        }
+    }
+}
+
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DisplaySettings {
+    mode: DisplayMode,
+    monitor: usize,
+    // where the window was last left while windowed
+    size: Option<Vec2>,
+    position: Option<IVec2>,
+}
+
+impl DisplaySettings {
+    pub fn set_size(&mut self, size: Vec2) {
+        self.size = Some(size);
+    }
+}
+
+// F11 cycles windowed, borderless and exclusive fullscreen, F10 moves to the next monitor.
+fn cycle_display(
+    mut commands: Commands,
+    locale: Res<Locale>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    monitors: Query<(), With<Monitor>>,
+    mut progress: ResMut<Progress>,
+) {
+    let display = &mut progress.display;
+    if keyboard_input.just_pressed(KeyCode::F11) {
+        display.mode = display.mode.next();
+        show_toast(&mut commands, locale.text(display.mode.key()));
+    } else if keyboard_input.just_pressed(KeyCode::F10) {
+        display.monitor = (display.monitor + 1) % monitors.iter().count().max(1);
+        // centre on the new monitor rather than keep the old one's coordinates
+        display.position = None;
+        show_toast(
+            &mut commands,
+            locale.format("toast-monitor", &[("monitor", &(display.monitor + 1))]),
+        );
+    } else {
+        return;
+    }
+    progress.save();
+}
+
+fn apply_display(progress: Res<Progress>, mut window: Single<&mut Window, With<PrimaryWindow>>) {
+    if !progress.is_changed() {
+        return;
+    }
+
+    let display = &progress.display;
+    let monitor = MonitorSelection::Index(display.monitor);
+    let mode = match display.mode {
+        DisplayMode::Windowed => WindowMode::Windowed,
+        DisplayMode::Borderless => WindowMode::BorderlessFullscreen(monitor),
+        DisplayMode::Fullscreen => WindowMode::Fullscreen(monitor),
+    };
+    if window.mode != mode {
+        window.mode = mode;
+    }
+    if display.mode != DisplayMode::Windowed {
+        return;
+    }
+
+    if let Some(size) = display.size {
+        if window.resolution.size() != size {
+            window.resolution.set(size.x, size.y);
+        }
+    }
+    let position = match display.position {
+        Some(position) => WindowPosition::At(position),
+        None => WindowPosition::Centered(monitor),
+    };
+    if window.position != position {
+        window.position = position;
+    }
+}
+
+// Resizes and moves come in bursts while dragging, so only save once they settle.
+fn remember_window(
+    time: Res<Time>,
+    primary: Single<Entity, With<PrimaryWindow>>,
+    mut resized_reader: EventReader<WindowResized>,
+    mut moved_reader: EventReader<WindowMoved>,
+    mut progress: ResMut<Progress>,
+    mut settle: Local<Option<Timer>>,
+) {
+    let resized = resized_reader
+        .read()
+        .filter(|e| e.window == *primary)
+        .last()
+        .map(|e| Vec2::new(e.width, e.height));
+    let moved = moved_reader
+        .read()
+        .filter(|e| e.window == *primary)
+        .last()
+        .map(|e| e.position);
+    if progress.display.mode == DisplayMode::Windowed {
+        if resized.is_some() && progress.display.size != resized {
+            progress.display.size = resized;
+            *settle = Some(Timer::from_seconds(1.0, TimerMode::Once));
+        }
+        if moved.is_some() && progress.display.position != moved {
+            progress.display.position = moved;
+            *settle = Some(Timer::from_seconds(1.0, TimerMode::Once));
+        }
+    }
+
+    if settle
+        .as_mut()
+        .is_some_and(|timer| timer.tick(time.delta()).finished())
+    {
+        *settle = None;
+        progress.save();
+    }
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Vsync {
+    #[default]
+    On,
+    Off,
+    Mailbox,
+}
+
+impl Vsync {
+    fn next(self) -> Self {
+        match self {
+            Self::On => Self::Off,
+            Self::Off => Self::Mailbox,
+            Self::Mailbox => Self::On,
+        }
+    }
+
+    fn key(self) -> &'static str {
+        match self {
+            Self::On => "vsync-on",
+            Self::Off => "vsync-off",
+            Self::Mailbox => "vsync-mailbox",
+        }
+    }
+
+    fn present_mode(self) -> PresentMode {
+        match self {
+            Self::On => PresentMode::AutoVsync,
+            Self::Off => PresentMode::AutoNoVsync,
+            Self::Mailbox => PresentMode::Mailbox,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphicsSettings {
+    vsync: Vsync,
+    fps_cap: Option<u32>,
+}
+
+// F9 cycles vsync modes, F8 steps through the frame rate caps.
+fn cycle_graphics(
+    mut commands: Commands,
+    locale: Res<Locale>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut progress: ResMut<Progress>,
+) {
+    let graphics = &mut progress.graphics;
+    if keyboard_input.just_pressed(KeyCode::F9) {
+        graphics.vsync = graphics.vsync.next();
+        show_toast(&mut commands, locale.text(graphics.vsync.key()));
+    } else if keyboard_input.just_pressed(KeyCode::F8) {
+        let current = FPS_CAPS.iter().position(|cap| *cap == graphics.fps_cap);
+        graphics.fps_cap = FPS_CAPS[current.map_or(0, |i| (i + 1) % FPS_CAPS.len())];
+        let message = match graphics.fps_cap {
+            Some(fps) => locale.format("toast-fps-cap", &[("fps", &fps)]),
+            None => locale.text("toast-fps-uncapped").to_string(),
+        };
+        show_toast(&mut commands, message);
+    } else {
+        return;
+    }
+    progress.save();
+}
+
+fn apply_present_mode(
+    progress: Res<Progress>,
+    mut window: Single<&mut Window, With<PrimaryWindow>>,
+) {
+    let present_mode = progress.graphics.vsync.present_mode();
+    if progress.is_changed() && window.present_mode != present_mode {
+        window.present_mode = present_mode;
+    }
+}
+
+// Sleeps off whatever is left of the frame budget, the simulation only
+// ticks four times a second so there's no point rendering hundreds of frames.
+fn limit_frame_rate(progress: Res<Progress>, mut last_frame: Local<Option<Instant>>) {
+    if let (Some(fps), Some(last_frame)) = (progress.graphics.fps_cap, *last_frame) {
+        let budget = Duration::from_secs_f64(1.0 / fps as f64);
+        if let Some(remaining) = budget.checked_sub(last_frame.elapsed()) {
+            thread::sleep(remaining);
+        }
+    }
+    *last_frame = Some(Instant::now());
+}
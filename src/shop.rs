@@ -0,0 +1,236 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    locale::Locale,
+    only_menu,
+    progress::{Progress, Skin},
+    seasons::Seasons,
+    GameOverEvent, Menu, Player, Shield, SnakeHead,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ArenaTheme {
+    Default,
+    Midnight,
+    Forest,
+}
+
+impl ArenaTheme {
+    fn name(self, locale: &Locale) -> &str {
+        locale.text(match self {
+            Self::Default => "theme-default",
+            Self::Midnight => "theme-midnight",
+            Self::Forest => "theme-forest",
+        })
+    }
+
+    fn clear_color(self) -> Color {
+        match self {
+            Self::Default => Color::srgb(0.04, 0.04, 0.04),
+            Self::Midnight => Color::srgb(0.02, 0.03, 0.12),
+            Self::Forest => Color::srgb(0.03, 0.1, 0.04),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum ShopItem {
+    Skin(Skin),
+    Theme(ArenaTheme),
+    StartingShield,
+}
+
+// Everything for sale and its price in coins.
+const SHOP_ITEMS: [(ShopItem, u32); 7] = [
+    (ShopItem::Skin(Skin::Neon), 300),
+    (ShopItem::Skin(Skin::Ember), 200),
+    (ShopItem::Skin(Skin::Ghost), 150),
+    (ShopItem::Theme(ArenaTheme::Default), 0),
+    (ShopItem::Theme(ArenaTheme::Midnight), 100),
+    (ShopItem::Theme(ArenaTheme::Forest), 100),
+    (ShopItem::StartingShield, 50),
+];
+
+const SHOP_KEYS: [KeyCode; 7] = [
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+];
+
+#[derive(Component)]
+struct Shop;
+
+// Set when a run starts so the next player one snake gets a bought shield.
+#[derive(Resource)]
+struct StartingPowerUpPending(bool);
+
+pub struct ShopPlugin;
+
+impl Plugin for ShopPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(StartingPowerUpPending(true))
+            .add_systems(
+                Update,
+                (
+                    (toggle_shop, shop_purchase, update_shop).chain(),
+                    apply_theme,
+                    grant_starting_power_up,
+                ),
+            );
+    }
+}
+
+fn toggle_shop(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    shop: Query<Entity, With<Shop>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::KeyB) {
+        return;
+    }
+
+    match shop.get_single() {
+        Ok(ent) => commands.entity(ent).despawn(),
+        Err(_) => {
+            commands.spawn((
+                Text::default(),
+                Node {
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Px(5.0),
+                    right: Val::Px(5.0),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+                Shop,
+                Menu,
+            ));
+        }
+    }
+}
+
+fn shop_purchase(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut progress: ResMut<Progress>,
+    shop: Query<Entity, With<Shop>>,
+    menus: Query<Entity, With<Menu>>,
+) {
+    if !shop.get_single().is_ok_and(|ent| only_menu(ent, &menus)) {
+        return;
+    }
+
+    for (key, (item, price)) in SHOP_KEYS.iter().zip(SHOP_ITEMS) {
+        if !keyboard_input.just_pressed(*key) {
+            continue;
+        }
+
+        // owned skins and themes are equipped again for free
+        let owned = match item {
+            ShopItem::Skin(skin) => progress.unlocked.contains(&skin),
+            ShopItem::Theme(theme) => progress.themes.contains(&theme),
+            ShopItem::StartingShield => false,
+        };
+        if !owned {
+            if progress.coins < price {
+                continue;
+            }
+            progress.coins -= price;
+        }
+
+        match item {
+            ShopItem::Skin(skin) => {
+                if !owned {
+                    progress.unlocked.push(skin);
+                }
+                progress.selected = skin;
+            }
+            ShopItem::Theme(theme) => {
+                if !owned {
+                    progress.themes.push(theme);
+                }
+                progress.theme = theme;
+            }
+            ShopItem::StartingShield => progress.starting_shields += 1,
+        }
+        progress.save();
+    }
+}
+
+fn update_shop(
+    locale: Res<Locale>,
+    progress: Res<Progress>,
+    mut shop: Query<&mut Text, With<Shop>>,
+) {
+    for mut text in shop.iter_mut() {
+        let mut lines = vec![locale.format("shop-title", &[("coins", &progress.coins)])];
+        for (i, (item, price)) in SHOP_ITEMS.iter().enumerate() {
+            let line = match item {
+                ShopItem::Skin(skin) if progress.unlocked.contains(skin) => {
+                    locale.format("shop-skin-owned", &[("skin", &skin.name(&locale))])
+                }
+                ShopItem::Skin(skin) => locale.format(
+                    "shop-skin",
+                    &[("skin", &skin.name(&locale)), ("price", price)],
+                ),
+                ShopItem::Theme(theme) if progress.themes.contains(theme) => {
+                    locale.format("shop-theme-owned", &[("theme", &theme.name(&locale))])
+                }
+                ShopItem::Theme(theme) => locale.format(
+                    "shop-theme",
+                    &[("theme", &theme.name(&locale)), ("price", price)],
+                ),
+                ShopItem::StartingShield => locale.format(
+                    "shop-starting-shield",
+                    &[("price", price), ("banked", &progress.starting_shields)],
+                ),
+            };
+            lines.push(format!("{}. {line}", i + 1));
+        }
+        text.0 = lines.join("\n");
+    }
+}
+
+// A seasonal event only recolours the default theme, never a bought one.
+fn apply_theme(
+    progress: Res<Progress>,
+    seasons: Res<Seasons>,
+    mut clear_color: ResMut<ClearColor>,
+) {
+    if !progress.is_changed() && !seasons.is_changed() {
+        return;
+    }
+
+    clear_color.0 = match (progress.theme, seasons.clear_color()) {
+        (ArenaTheme::Default, Some(color)) => color,
+        (theme, _) => theme.clear_color(),
+    };
+}
+
+fn grant_starting_power_up(
+    mut commands: Commands,
+    mut pending: ResMut<StartingPowerUpPending>,
+    mut progress: ResMut<Progress>,
+    mut game_over_reader: EventReader<GameOverEvent>,
+    heads: Query<(Entity, &SnakeHead), Added<SnakeHead>>,
+) {
+    if game_over_reader.read().next().is_some() {
+        pending.0 = true;
+    }
+    if !pending.0 {
+        return;
+    }
+
+    let Some((head_entity, _)) = heads.iter().find(|(_, head)| head.player == Player::One) else {
+        return;
+    };
+    pending.0 = false;
+    if progress.starting_shields > 0 {
+        progress.starting_shields -= 1;
+        commands.entity(head_entity).insert(Shield);
+        progress.save();
+    }
+}
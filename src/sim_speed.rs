@@ -0,0 +1,127 @@
+use bevy::prelude::*;
+use std::time::Duration;
+
+use crate::{locale::Locale, show_toast, snake_movement, FixedTimer};
+
+pub const MIN_SCALE: f32 = 0.25;
+pub const MAX_SCALE: f32 = 8.0;
+
+// How fast the simulation runs against real time. Everything on virtual
+// time follows it, the movement tick and the animations alike.
+#[derive(Resource)]
+pub struct SimSpeed {
+    scale: f32,
+    // effects like the death camera's slow down on top of the chosen speed
+    slow_motion: f32,
+    step_requested: bool,
+    // unpaused for a single tick, paused again once it's run
+    stepping: bool,
+}
+
+impl SimSpeed {
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    pub fn set_scale(&mut self, scale: f32) {
+        self.scale = scale.clamp(MIN_SCALE, MAX_SCALE);
+    }
+
+    pub fn set_slow_motion(&mut self, factor: f32) {
+        self.slow_motion = factor;
+    }
+
+    // Runs one more movement tick, if the game is paused.
+    pub fn step(&mut self) {
+        self.step_requested = true;
+    }
+}
+
+// The speed itself is always there for anything to set. The hotkeys come
+// with the `debug-tools` feature: Page Up and Page Down double or halve the
+// speed, Home puts it back to normal, Pause pauses and End steps a tick.
+pub struct SimSpeedPlugin;
+
+impl Plugin for SimSpeedPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(SimSpeed {
+            scale: 1.0,
+            slow_motion: 1.0,
+            step_requested: false,
+            stepping: false,
+        })
+        .add_systems(Update, (apply_scale, step_tick.after(snake_movement)));
+
+        if cfg!(feature = "debug-tools") {
+            app.add_systems(Update, speed_hotkeys.before(apply_scale));
+        }
+    }
+}
+
+fn apply_scale(speed: Res<SimSpeed>, mut time: ResMut<Time<Virtual>>) {
+    if speed.is_changed() {
+        time.set_relative_speed(speed.scale * speed.slow_motion);
+    }
+}
+
+fn step_tick(
+    mut speed: ResMut<SimSpeed>,
+    mut time: ResMut<Time<Virtual>>,
+    mut timer: ResMut<FixedTimer>,
+) {
+    if speed.stepping {
+        if timer.0.just_finished() {
+            time.pause();
+            speed.stepping = false;
+        }
+        return;
+    }
+    if !speed.step_requested {
+        return;
+    }
+
+    speed.step_requested = false;
+    if time.is_paused() {
+        // the next frame's delta, however small, finishes the tick
+        let almost = timer.0.duration().saturating_sub(Duration::from_nanos(1));
+        timer.0.set_elapsed(almost);
+        time.unpause();
+        speed.stepping = true;
+    }
+}
+
+fn speed_hotkeys(
+    mut commands: Commands,
+    locale: Res<Locale>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut speed: ResMut<SimSpeed>,
+    mut time: ResMut<Time<Virtual>>,
+) {
+    let scale = if keyboard_input.just_pressed(KeyCode::PageUp) {
+        speed.scale() * 2.0
+    } else if keyboard_input.just_pressed(KeyCode::PageDown) {
+        speed.scale() / 2.0
+    } else if keyboard_input.just_pressed(KeyCode::Home) {
+        1.0
+    } else {
+        speed.scale()
+    };
+    if scale != speed.scale() {
+        speed.set_scale(scale);
+        show_toast(
+            &mut commands,
+            locale.format("toast-sim-speed", &[("scale", &speed.scale())]),
+        );
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Pause) {
+        if time.is_paused() {
+            time.unpause();
+        } else {
+            time.pause();
+        }
+    }
+    if keyboard_input.just_pressed(KeyCode::End) {
+        speed.step();
+    }
+}
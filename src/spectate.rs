@@ -0,0 +1,175 @@
+use bevy::prelude::*;
+use bevy::tasks::{block_on, poll_once, IoTaskPool, Task};
+use serde::Serialize;
+use std::time::Duration;
+
+use crate::{
+    arg_value, obstacles::Block, Food, GameMode, Lives, Player, Position, PowerUp, Score,
+    SnakeHead, SnakeSegments, Trail, ARENA_HEIGHT, ARENA_WIDTH,
+};
+
+const SNAPSHOT_SECS: f32 = 0.2;
+// A hung endpoint drops the snapshot after this long so the next can go.
+const SNAPSHOT_TIMEOUT: Duration = Duration::from_secs(2);
+
+// A cell as `[x, y]`, which keeps snapshots small.
+type Cell = (i32, i32);
+
+// Everything a spectator page needs to draw the arena as it is now.
+#[derive(Serialize)]
+struct Snapshot {
+    // counts up so a page can drop snapshots that arrive out of order
+    sequence: u64,
+    mode: GameMode,
+    width: u32,
+    height: u32,
+    score: u32,
+    lives: u32,
+    // head first
+    snakes: Vec<(Player, Vec<Cell>)>,
+    food: Vec<Cell>,
+    power_ups: Vec<(PowerUp, Cell)>,
+    trails: Vec<(Player, Cell)>,
+    blocks: Vec<Cell>,
+}
+
+impl Snapshot {
+    fn capture(world: &mut World, sequence: u64) -> Self {
+        let cell = |pos: &Position| (pos.x, pos.y);
+        let mut positions = world.query::<&Position>();
+        let mut heads = world.query::<(&SnakeHead, &SnakeSegments)>();
+        let mut snakes: Vec<(Player, Vec<Cell>)> = heads
+            .iter(world)
+            .map(|(head, segments)| {
+                let cells = segments
+                    .0
+                    .iter()
+                    .filter_map(|ent| positions.get(world, *ent).ok().map(cell))
+                    .collect();
+                (head.player, cells)
+            })
+            .collect();
+        snakes.sort_by_key(|(player, _)| *player);
+
+        Self {
+            sequence,
+            mode: *world.resource::<GameMode>(),
+            width: ARENA_WIDTH,
+            height: ARENA_HEIGHT,
+            score: world.resource::<Score>().0,
+            lives: world.resource::<Lives>().0,
+            snakes,
+            food: world
+                .query_filtered::<&Position, With<Food>>()
+                .iter(world)
+                .map(cell)
+                .collect(),
+            power_ups: world
+                .query::<(&PowerUp, &Position)>()
+                .iter(world)
+                .map(|(power_up, pos)| (*power_up, cell(pos)))
+                .collect(),
+            trails: world
+                .query::<(&Trail, &Position)>()
+                .iter(world)
+                .map(|(trail, pos)| (trail.0, cell(pos)))
+                .collect(),
+            blocks: world
+                .query_filtered::<&Position, With<Block>>()
+                .iter(world)
+                .map(cell)
+                .collect(),
+        }
+    }
+}
+
+// Live view of a run for streaming overlays and tournaments. With
+// `--spectate-url` a snapshot is posted there as JSON a few times a second,
+// for a companion page or relay to draw. Only one is ever in flight, a
+// slow endpoint gets fewer rather than a backlog.
+#[derive(Resource)]
+struct Spectate {
+    url: String,
+    agent: ureq::Agent,
+    timer: Timer,
+    sequence: u64,
+    pending: Option<Task<Result<(), String>>>,
+    // failures are logged once rather than at every snapshot
+    failing: bool,
+}
+
+pub struct SpectatePlugin;
+
+impl Plugin for SpectatePlugin {
+    fn build(&self, app: &mut App) {
+        let Some(url) = arg_value("--spectate-url") else {
+            return;
+        };
+
+        app.insert_resource(Spectate {
+            url,
+            agent: ureq::AgentBuilder::new()
+                .timeout_connect(SNAPSHOT_TIMEOUT)
+                .timeout(SNAPSHOT_TIMEOUT)
+                .build(),
+            timer: Timer::from_seconds(SNAPSHOT_SECS, TimerMode::Repeating),
+            sequence: 0,
+            pending: None,
+            failing: false,
+        })
+        .add_systems(Last, (send_snapshot, finish_snapshot).chain());
+    }
+}
+
+fn send_snapshot(world: &mut World) {
+    let delta = world.resource::<Time<Real>>().delta();
+    let spectate = world.resource_mut::<Spectate>().into_inner();
+    if !spectate.timer.tick(delta).just_finished() || spectate.pending.is_some() {
+        return;
+    }
+    spectate.sequence += 1;
+    let (agent, url, sequence) = (
+        spectate.agent.clone(),
+        spectate.url.clone(),
+        spectate.sequence,
+    );
+
+    let body = match serde_json::to_string(&Snapshot::capture(world, sequence)) {
+        Ok(body) => body,
+        Err(err) => {
+            warn!("Failed to serialize spectator snapshot: {err}");
+            return;
+        }
+    };
+    world.resource_mut::<Spectate>().pending = Some(IoTaskPool::get().spawn(async move {
+        agent
+            .post(&url)
+            .set("Content-Type", "application/json")
+            .send_string(&body)
+            .map(|_| ())
+            .map_err(|err| err.to_string())
+    }));
+}
+
+fn finish_snapshot(mut spectate: ResMut<Spectate>) {
+    let Some(task) = spectate.pending.as_mut() else {
+        return;
+    };
+    let Some(outcome) = block_on(poll_once(task)) else {
+        return;
+    };
+    spectate.pending = None;
+
+    match outcome {
+        Ok(()) if spectate.failing => {
+            info!("Spectator snapshots are getting through again");
+            spectate.failing = false;
+        }
+        Ok(()) => {}
+        Err(err) if !spectate.failing => {
+            warn!("Spectator snapshot failed: {err}");
+            spectate.failing = true;
+        }
+        Err(_) => {}
+    }
+}
@@ -0,0 +1,89 @@
+use bevy::{prelude::*, render::camera::Viewport, window::PrimaryWindow};
+
+use crate::{
+    cell_center, progress::Progress, Player, Position, SnakeHead, SnakeSegments, ARENA_WIDTH,
+};
+
+// Share of the window's width and height the inset takes up.
+const INSET_FRACTION: u32 = 4;
+const INSET_MARGIN: u32 = 8;
+// Cells visible across the inset.
+const INSET_CELLS: f32 = 4.0;
+// Shorter snakes have their tail in plain sight anyway.
+const MIN_LENGTH: usize = 12;
+
+// Second camera drawn into a corner of the window, following the end of
+// player one's tail on long runs.
+#[derive(Component)]
+pub struct TailCamera;
+
+// The camera looking at the whole arena in the main window.
+pub type MainCamera = (With<Camera2d>, With<IsDefaultUiCamera>, Without<TailCamera>);
+
+pub struct TailCameraPlugin;
+
+impl Plugin for TailCameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (toggle_tail_camera, follow_tail).chain());
+    }
+}
+
+fn toggle_tail_camera(
+    mut commands: Commands,
+    progress: Res<Progress>,
+    cameras: Query<Entity, With<TailCamera>>,
+) {
+    match cameras.get_single() {
+        Ok(ent) if !progress.tail_camera => commands.entity(ent).despawn(),
+        Err(_) if progress.tail_camera => {
+            commands.spawn((
+                Camera2d,
+                Camera {
+                    order: 1,
+                    is_active: false,
+                    clear_color: ClearColorConfig::Custom(Color::srgb(0.1, 0.1, 0.1)),
+                    ..default()
+                },
+                TailCamera,
+            ));
+        }
+        _ => {}
+    }
+}
+
+fn follow_tail(
+    progress: Res<Progress>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    heads: Query<(&SnakeHead, &SnakeSegments)>,
+    positions: Query<&Position>,
+    mut cameras: Query<
+        (&mut Camera, &mut Transform, &mut OrthographicProjection),
+        With<TailCamera>,
+    >,
+) {
+    let Ok((mut camera, mut transform, mut projection)) = cameras.get_single_mut() else {
+        return;
+    };
+
+    let tail = heads
+        .iter()
+        .find(|(head, segments)| head.player == Player::One && segments.0.len() >= MIN_LENGTH)
+        .and_then(|(_, segments)| positions.get(*segments.0.last()?).ok());
+    // kept in the bottom right corner, sized to the window as it is now
+    let corner = UVec2::new(window.physical_width(), window.physical_height());
+    let size = corner / INSET_FRACTION;
+    let Some(tail) = tail.filter(|_| size.min_element() > 0) else {
+        camera.is_active = false;
+        return;
+    };
+
+    camera.is_active = true;
+    camera.viewport = Some(Viewport {
+        physical_position: corner.saturating_sub(size + UVec2::splat(INSET_MARGIN)),
+        physical_size: size,
+        ..default()
+    });
+    projection.scale = INSET_CELLS * INSET_FRACTION as f32 / ARENA_WIDTH as f32;
+    transform.translation =
+        cell_center(*tail, progress.orientation, &window).extend(transform.translation.z);
+}
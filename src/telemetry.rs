@@ -0,0 +1,278 @@
+use bevy::prelude::*;
+use bevy::tasks::{block_on, poll_once, IoTaskPool, Task};
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+use crate::{
+    arg_value, locale::Locale, only_menu, profiles::base_dir, progress::Progress, GameOverEvent,
+    Menu, SnakeSegments,
+};
+
+// Present while the game runs, so finding it on launch means the last
+// session didn't exit cleanly.
+const RUNNING_MARKER: &str = "running.lock";
+const BATCH_RUNS: u32 = 10;
+
+// Off unless the player turns it on from the T panel.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct TelemetrySettings {
+    enabled: bool,
+    pending: TelemetryBatch,
+}
+
+// Totals since the last report, nothing else is kept or sent.
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+struct TelemetryBatch {
+    runs: u32,
+    total_length: u64,
+    crashes: u32,
+}
+
+impl TelemetryBatch {
+    fn is_empty(&self) -> bool {
+        self.runs == 0 && self.crashes == 0
+    }
+
+    fn report(&self) -> Report {
+        Report {
+            version: env!("CARGO_PKG_VERSION"),
+            runs: self.runs,
+            average_length: if self.runs == 0 {
+                0.0
+            } else {
+                self.total_length as f32 / self.runs as f32
+            },
+            crashes: self.crashes,
+        }
+    }
+
+    // Takes off what has been reported, keeping anything added meanwhile.
+    fn subtract(&mut self, sent: &Self) {
+        self.runs = self.runs.saturating_sub(sent.runs);
+        self.total_length = self.total_length.saturating_sub(sent.total_length);
+        self.crashes = self.crashes.saturating_sub(sent.crashes);
+    }
+}
+
+#[derive(Serialize)]
+struct Report {
+    version: &'static str,
+    runs: u32,
+    average_length: f32,
+    crashes: u32,
+}
+
+// Endpoint given with `--telemetry-url`, without it nothing is ever sent.
+#[derive(Resource)]
+struct TelemetryEndpoint(Option<String>);
+
+#[derive(Resource, Default)]
+struct PendingReport(Option<Task<Result<TelemetryBatch, String>>>);
+
+#[derive(Component)]
+struct TelemetryPanel;
+
+pub struct TelemetryPlugin;
+
+impl Plugin for TelemetryPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(TelemetryEndpoint(arg_value("--telemetry-url")))
+            .insert_resource(PendingReport::default())
+            .add_systems(Startup, check_last_exit)
+            .add_systems(
+                Update,
+                (
+                    record_runs,
+                    (send_report, finish_report).chain(),
+                    (
+                        toggle_telemetry_panel,
+                        toggle_telemetry,
+                        update_telemetry_panel,
+                    )
+                        .chain(),
+                ),
+            )
+            .add_systems(Last, clear_running_marker);
+    }
+}
+
+fn check_last_exit(mut progress: ResMut<Progress>) {
+    let Some(marker) = base_dir().map(|dir| dir.join(RUNNING_MARKER)) else {
+        return;
+    };
+    if marker.exists() && progress.telemetry.enabled {
+        progress.telemetry.pending.crashes += 1;
+        progress.save();
+    }
+    if let Err(err) = marker
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|_| fs::write(&marker, ""))
+    {
+        warn!("Failed to write {}: {err}", marker.display());
+    }
+}
+
+fn clear_running_marker(mut exit_reader: EventReader<AppExit>) {
+    if exit_reader.read().next().is_none() {
+        return;
+    }
+    if let Some(marker) = base_dir().map(|dir| dir.join(RUNNING_MARKER)) {
+        let _ = fs::remove_file(marker);
+    }
+}
+
+fn record_runs(
+    mut progress: ResMut<Progress>,
+    mut game_over_reader: EventReader<GameOverEvent>,
+    snakes: Query<&SnakeSegments>,
+) {
+    if game_over_reader.read().next().is_none() || !progress.telemetry.enabled {
+        return;
+    }
+
+    let length = snakes.iter().map(|segments| segments.0.len()).max();
+    let pending = &mut progress.telemetry.pending;
+    pending.runs += 1;
+    pending.total_length += length.unwrap_or(0) as u64;
+    progress.save();
+}
+
+// Reports once on launch if anything is waiting, then every few runs.
+fn send_report(
+    endpoint: Res<TelemetryEndpoint>,
+    progress: Res<Progress>,
+    mut pending: ResMut<PendingReport>,
+    mut sent_on_launch: Local<bool>,
+) {
+    let Some(url) = &endpoint.0 else {
+        return;
+    };
+    let batch = progress.telemetry.pending;
+    if !progress.telemetry.enabled || batch.is_empty() || pending.0.is_some() {
+        return;
+    }
+    if *sent_on_launch && batch.runs < BATCH_RUNS {
+        return;
+    }
+    *sent_on_launch = true;
+
+    let body = match serde_json::to_string(&batch.report()) {
+        Ok(body) => body,
+        Err(err) => {
+            warn!("Failed to serialize telemetry: {err}");
+            return;
+        }
+    };
+    let url = url.clone();
+    pending.0 = Some(IoTaskPool::get().spawn(async move {
+        ureq::post(&url)
+            .set("Content-Type", "application/json")
+            .send_string(&body)
+            .map(|_| batch)
+            .map_err(|err| err.to_string())
+    }));
+}
+
+fn finish_report(mut pending: ResMut<PendingReport>, mut progress: ResMut<Progress>) {
+    let Some(task) = pending.0.as_mut() else {
+        return;
+    };
+    let Some(outcome) = block_on(poll_once(task)) else {
+        return;
+    };
+    pending.0 = None;
+
+    match outcome {
+        Ok(sent) => {
+            progress.telemetry.pending.subtract(&sent);
+            progress.save();
+        }
+        // kept for the next try, failures aren't worth bothering the player with
+        Err(err) => warn!("Telemetry report failed: {err}"),
+    }
+}
+
+fn toggle_telemetry_panel(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    panel: Query<Entity, With<TelemetryPanel>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::KeyT) {
+        return;
+    }
+
+    match panel.get_single() {
+        Ok(ent) => commands.entity(ent).despawn(),
+        Err(_) => {
+            commands.spawn((
+                Text::default(),
+                Node {
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Px(5.0),
+                    left: Val::Px(5.0),
+                    max_width: Val::Percent(60.0),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+                TelemetryPanel,
+                Menu,
+            ));
+        }
+    }
+}
+
+fn toggle_telemetry(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut progress: ResMut<Progress>,
+    panel: Query<Entity, With<TelemetryPanel>>,
+    menus: Query<Entity, With<Menu>>,
+) {
+    // consent is only ever changed on this panel, never by another menu's key
+    if !panel.get_single().is_ok_and(|ent| only_menu(ent, &menus))
+        || !keyboard_input.just_pressed(KeyCode::Digit1)
+    {
+        return;
+    }
+
+    let telemetry = &mut progress.telemetry;
+    telemetry.enabled = !telemetry.enabled;
+    // turning it off drops anything that was waiting to go
+    if !telemetry.enabled {
+        telemetry.pending = TelemetryBatch::default();
+    }
+    progress.save();
+}
+
+fn update_telemetry_panel(
+    locale: Res<Locale>,
+    endpoint: Res<TelemetryEndpoint>,
+    progress: Res<Progress>,
+    mut panel: Query<&mut Text, With<TelemetryPanel>>,
+) {
+    for mut text in panel.iter_mut() {
+        let telemetry = &progress.telemetry;
+        let destination = match &endpoint.0 {
+            Some(url) => locale.format("telemetry-endpoint", &[("url", url)]),
+            None => locale.text("telemetry-no-endpoint").to_string(),
+        };
+        // exactly the body that would be posted next
+        let preview = serde_json::to_string_pretty(&telemetry.pending.report())
+            .unwrap_or_else(|err| err.to_string());
+        text.0 = [
+            locale.text("telemetry-title").to_string(),
+            locale.text("telemetry-description").to_string(),
+            locale
+                .text(if telemetry.enabled {
+                    "telemetry-on"
+                } else {
+                    "telemetry-off"
+                })
+                .to_string(),
+            destination,
+            locale.text("telemetry-preview").to_string(),
+            preview,
+        ]
+        .join("\n");
+    }
+}
@@ -0,0 +1,133 @@
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+use crate::{locale::Locale, saved_run::SavedRun, FixedTimer, Menu};
+
+const HISTORY_TICKS: usize = 200;
+
+// The last few hundred ticks, newest at the back. While scrubbing, `cursor`
+// is the snapshot on screen and the game is paused.
+#[derive(Resource, Default)]
+struct TickHistory {
+    snapshots: VecDeque<SavedRun>,
+    cursor: Option<usize>,
+}
+
+#[derive(Component)]
+struct TimeTravelPanel;
+
+// Debugging aid, built in with the `debug-tools` feature. F6 pauses and
+// opens the panel, comma and period step back and forward through recent
+// ticks, and F6 again carries on from the tick on screen.
+pub struct TimeTravelPlugin;
+
+impl Plugin for TimeTravelPlugin {
+    fn build(&self, app: &mut App) {
+        if !cfg!(feature = "debug-tools") {
+            return;
+        }
+
+        app.insert_resource(TickHistory::default()).add_systems(
+            Update,
+            (record_tick, toggle_time_travel, scrub_ticks, update_panel).chain(),
+        );
+    }
+}
+
+fn record_tick(world: &mut World) {
+    if world.resource::<TickHistory>().cursor.is_some()
+        || !world.resource::<FixedTimer>().0.just_finished()
+    {
+        return;
+    }
+
+    let snapshot = SavedRun::capture(world);
+    let mut history = world.resource_mut::<TickHistory>();
+    if history.snapshots.len() == HISTORY_TICKS {
+        history.snapshots.pop_front();
+    }
+    history.snapshots.push_back(snapshot);
+}
+
+fn toggle_time_travel(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut time: ResMut<Time<Virtual>>,
+    mut history: ResMut<TickHistory>,
+    panel: Query<Entity, With<TimeTravelPanel>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::F6) {
+        return;
+    }
+
+    match history.cursor.take() {
+        // the ticks after the one resumed from never happened now
+        Some(cursor) => {
+            history.snapshots.truncate(cursor + 1);
+            for ent in panel.iter() {
+                commands.entity(ent).despawn();
+            }
+            time.unpause();
+        }
+        None if !history.snapshots.is_empty() => {
+            history.cursor = Some(history.snapshots.len() - 1);
+            commands.spawn((
+                Text::default(),
+                Node {
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Px(5.0),
+                    width: Val::Percent(100.0),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+                TimeTravelPanel,
+                Menu,
+            ));
+            time.pause();
+        }
+        None => {}
+    }
+}
+
+fn scrub_ticks(world: &mut World) {
+    let Some(cursor) = world.resource::<TickHistory>().cursor else {
+        return;
+    };
+    let keyboard_input = world.resource::<ButtonInput<KeyCode>>();
+    let last = world.resource::<TickHistory>().snapshots.len() - 1;
+    let target = if keyboard_input.just_pressed(KeyCode::Comma) {
+        cursor.saturating_sub(1)
+    } else if keyboard_input.just_pressed(KeyCode::Period) {
+        (cursor + 1).min(last)
+    } else {
+        return;
+    };
+    if target == cursor {
+        return;
+    }
+
+    let mut history = world.resource_mut::<TickHistory>();
+    history.cursor = Some(target);
+    let snapshot = history.snapshots[target].clone();
+    snapshot.restore(world);
+}
+
+fn update_panel(
+    locale: Res<Locale>,
+    history: Res<TickHistory>,
+    mut panel: Query<&mut Text, With<TimeTravelPanel>>,
+) {
+    let Some(cursor) = history.cursor else {
+        return;
+    };
+    for mut text in panel.iter_mut() {
+        text.0 = locale.format(
+            "time-travel",
+            &[
+                ("back", &(history.snapshots.len() - 1 - cursor)),
+                ("total", &history.snapshots.len()),
+            ],
+        );
+    }
+}
@@ -0,0 +1,143 @@
+use bevy::prelude::*;
+
+use crate::{
+    locale::Locale, projectile_hits, show_toast, snake_death, GameOverEvent, Position,
+    ReversalAttemptEvent, Shield, SnakeDiedEvent, SnakeHead, Toast, ARENA_HEIGHT, ARENA_WIDTH,
+};
+
+// Reversal attempts in one run before it's worth explaining.
+const REVERSAL_TIP_ATTEMPTS: u32 = 3;
+// Deaths against the same wall, across runs, before pointing it out.
+const WALL_TIP_DEATHS: u32 = 3;
+const SELF_TIP_DEATHS: u32 = 2;
+const TIP_SECS: f32 = 5.0;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Wall {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+impl Wall {
+    // The wall a head ended up beyond, if it left the arena.
+    fn hit_at(pos: Position) -> Option<Self> {
+        if pos.x < 0 {
+            Some(Self::Left)
+        } else if pos.x as u32 >= ARENA_WIDTH {
+            Some(Self::Right)
+        } else if pos.y < 0 {
+            Some(Self::Bottom)
+        } else if pos.y as u32 >= ARENA_HEIGHT {
+            Some(Self::Top)
+        } else {
+            None
+        }
+    }
+
+    fn name(self, locale: &Locale) -> &str {
+        locale.text(match self {
+            Self::Left => "wall-left",
+            Self::Right => "wall-right",
+            Self::Top => "wall-top",
+            Self::Bottom => "wall-bottom",
+        })
+    }
+}
+
+// What went wrong recently, looked over at game over for a tip to give.
+// Reversals are counted per run, deaths over the whole session since a
+// single run often only has the one.
+#[derive(Resource, Default)]
+struct Mistakes {
+    reversal_attempts: u32,
+    wall_deaths: Vec<(Wall, u32)>,
+    self_deaths: u32,
+}
+
+impl Mistakes {
+    fn take_tip(&mut self, locale: &Locale) -> Option<String> {
+        if let Some((wall, deaths)) = self
+            .wall_deaths
+            .iter_mut()
+            .find(|(_, deaths)| *deaths >= WALL_TIP_DEATHS)
+        {
+            *deaths = 0;
+            return Some(locale.format("tip-wall", &[("wall", &wall.name(locale))]));
+        }
+        if self.reversal_attempts >= REVERSAL_TIP_ATTEMPTS {
+            return Some(locale.text("tip-reversal").to_string());
+        }
+        if self.self_deaths >= SELF_TIP_DEATHS {
+            self.self_deaths = 0;
+            return Some(locale.text("tip-self").to_string());
+        }
+        None
+    }
+}
+
+pub struct TipsPlugin;
+
+impl Plugin for TipsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Mistakes>().add_systems(
+            Update,
+            (
+                track_mistakes.after(projectile_hits).before(snake_death),
+                show_tip,
+            ),
+        );
+    }
+}
+
+fn track_mistakes(
+    mut mistakes: ResMut<Mistakes>,
+    mut reversal_reader: EventReader<ReversalAttemptEvent>,
+    mut died_reader: EventReader<SnakeDiedEvent>,
+    heads: Query<(&Position, Has<Shield>), With<SnakeHead>>,
+) {
+    mistakes.reversal_attempts += reversal_reader.read().count() as u32;
+
+    for SnakeDiedEvent(head) in died_reader.read() {
+        // a shield saves the snake, so there's nothing to learn from it
+        let Ok((position, false)) = heads.get(*head) else {
+            continue;
+        };
+        match Wall::hit_at(*position) {
+            Some(wall) => match mistakes.wall_deaths.iter_mut().find(|(w, _)| *w == wall) {
+                Some((_, deaths)) => *deaths += 1,
+                None => mistakes.wall_deaths.push((wall, 1)),
+            },
+            None => mistakes.self_deaths += 1,
+        }
+    }
+}
+
+fn show_tip(
+    mut commands: Commands,
+    locale: Res<Locale>,
+    mut mistakes: ResMut<Mistakes>,
+    mut game_over_reader: EventReader<GameOverEvent>,
+) {
+    if game_over_reader.read().next().is_none() {
+        return;
+    }
+
+    // below the usual toasts, which game over tends to bring a few of,
+    // and up for long enough to read
+    if let Some(tip) = mistakes.take_tip(&locale) {
+        let toast = show_toast(&mut commands, tip);
+        commands.entity(toast).insert((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Percent(60.0),
+                width: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            Toast(Timer::from_seconds(TIP_SECS, TimerMode::Once)),
+        ));
+    }
+    mistakes.reversal_attempts = 0;
+}
@@ -0,0 +1,87 @@
+use bevy::prelude::*;
+
+use crate::{
+    locale::Locale, obstacles::Blocking, progress::Progress, show_toast, snake_growth, GameMode,
+    GameOverEvent, Menu, Position, ARENA_HEIGHT, ARENA_WIDTH,
+};
+
+#[derive(Component)]
+struct VictoryScreen;
+
+pub struct VictoryPlugin;
+
+impl Plugin for VictoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (detect_full_board.after(snake_growth), dismiss_victory),
+        );
+    }
+}
+
+// Filling every cell of the arena not walled off wins the run, there's
+// nowhere left for food.
+fn detect_full_board(
+    mut commands: Commands,
+    locale: Res<Locale>,
+    mode: Res<GameMode>,
+    mut time: ResMut<Time<Virtual>>,
+    mut progress: ResMut<Progress>,
+    segments: Query<&Position, Blocking>,
+    screen: Query<(), With<VictoryScreen>>,
+) {
+    if !matches!(
+        *mode,
+        GameMode::Classic | GameMode::Coop | GameMode::Arcade | GameMode::Objective
+    ) || !screen.is_empty()
+    {
+        return;
+    }
+
+    let mut cells: Vec<Position> = segments.iter().copied().collect();
+    cells.sort_by_key(|pos| (pos.x, pos.y));
+    cells.dedup();
+    if cells.len() < (ARENA_WIDTH * ARENA_HEIGHT) as usize {
+        return;
+    }
+
+    time.pause();
+    progress.record_perfect_game();
+    show_toast(&mut commands, locale.text("achievement-perfect-game"));
+    commands.spawn((
+        Text::new(locale.text("victory")),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Percent(30.0),
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        TextLayout::new_with_justify(JustifyText::Center),
+        TextFont::from_font_size(32.0),
+        TextColor(Color::srgb(1.0, 0.85, 0.2)),
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+        VictoryScreen,
+        Menu,
+    ));
+}
+
+// Enter starts a fresh run once the win has sunk in.
+fn dismiss_victory(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut time: ResMut<Time<Virtual>>,
+    screen: Query<Entity, With<VictoryScreen>>,
+    mut game_over_writer: EventWriter<GameOverEvent>,
+) {
+    let Ok(ent) = screen.get_single() else {
+        return;
+    };
+    if !keyboard_input.just_pressed(KeyCode::Enter) {
+        return;
+    }
+
+    commands.entity(ent).despawn();
+    time.unpause();
+    game_over_writer.send(GameOverEvent);
+}
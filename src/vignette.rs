@@ -0,0 +1,89 @@
+use bevy::prelude::*;
+use std::f32::consts::TAU;
+
+use crate::{
+    progress::Progress, Position, SnakeHead, SnakeSegments, StatusEffect, StatusEffects, Trail,
+};
+
+const VIGNETTE_WIDTH: f32 = 28.0;
+const VIGNETTE_MAX_ALPHA: f32 = 0.45;
+// how quickly the vignette fades in and out, per second
+const VIGNETTE_FADE: f32 = 6.0;
+const VIGNETTE_PULSE_HZ: f32 = 2.0;
+
+// How strongly the screen edges should warn the player, from 0 to 1.
+// Gameplay systems raise it and the vignette eases towards it.
+#[derive(Resource, Default)]
+pub struct Vignette {
+    pub danger: f32,
+}
+
+#[derive(Component)]
+struct VignetteFrame {
+    shown: f32,
+}
+
+pub struct VignettePlugin;
+
+impl Plugin for VignettePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Vignette::default())
+            .add_systems(Startup, spawn_vignette)
+            .add_systems(Update, (detect_danger, draw_vignette).chain());
+    }
+}
+
+fn spawn_vignette(mut commands: Commands) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            border: UiRect::all(Val::Px(VIGNETTE_WIDTH)),
+            ..default()
+        },
+        BorderColor(Color::NONE),
+        VignetteFrame { shown: 0.0 },
+    ));
+}
+
+// Danger when any snake's next step, if it keeps going, would kill it.
+fn detect_danger(
+    mut vignette: ResMut<Vignette>,
+    heads: Query<(&SnakeHead, &Position, &SnakeSegments, &StatusEffects)>,
+    positions: Query<&Position>,
+    trails: Query<&Position, With<Trail>>,
+) {
+    let in_danger = heads.iter().any(|(head, pos, segments, effects)| {
+        if effects.has(StatusEffect::Frozen) {
+            return false;
+        }
+        let next = pos.moved(head.direction);
+        // the tail moves out of the way on the same tick
+        let body = &segments.0[..segments.0.len().saturating_sub(1)];
+        !next.in_arena()
+            || body.iter().any(|ent| positions.get(*ent) == Ok(&next))
+            || trails.iter().any(|trail| *trail == next)
+    });
+    vignette.danger = if in_danger { 1.0 } else { 0.0 };
+}
+
+fn draw_vignette(
+    time: Res<Time<Real>>,
+    vignette: Res<Vignette>,
+    progress: Res<Progress>,
+    mut frame: Single<(&mut VignetteFrame, &mut BorderColor)>,
+) {
+    let (frame, border) = &mut *frame;
+    let step = VIGNETTE_FADE * time.delta_secs();
+    frame.shown += (vignette.danger - frame.shown).clamp(-step, step);
+
+    // a steady glow instead of a pulse with reduced motion on
+    let pulse = if progress.reduced_motion {
+        1.0
+    } else {
+        0.75 + 0.25 * (time.elapsed_secs() * VIGNETTE_PULSE_HZ * TAU).sin()
+    };
+    let alpha = VIGNETTE_MAX_ALPHA * frame.shown * pulse;
+    border.0 = Color::srgba(0.8, 0.0, 0.0, alpha);
+}
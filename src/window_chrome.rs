@@ -0,0 +1,89 @@
+use bevy::{
+    prelude::*,
+    window::{PrimaryWindow, SystemCursorIcon},
+    winit::{cursor::CursorIcon, WinitWindows},
+};
+use winit::window::Icon;
+
+use crate::{pickups::Pickups, Menu, SNAKE_HEAD_COLOR, SNAKE_SEGMENT_COLOR};
+
+const ICON_CELL: usize = 8;
+
+// A little snake chasing a pellet, drawn on a 4x4 grid of the game's own
+// colours so there's no image file to ship.
+const ICON: [&str; 4] = ["HSS.", "..S.", "..SS", "F..."];
+
+pub struct WindowChromePlugin;
+
+impl Plugin for WindowChromePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (set_window_icon, update_cursor));
+    }
+}
+
+fn icon(food_color: Color) -> Option<Icon> {
+    let size = ICON.len() * ICON_CELL;
+    let mut rgba = vec![0; size * size * 4];
+    for (row, cells) in ICON.iter().enumerate() {
+        for (col, cell) in cells.chars().enumerate() {
+            let color = match cell {
+                'H' => SNAKE_HEAD_COLOR,
+                'S' => SNAKE_SEGMENT_COLOR,
+                'F' => food_color,
+                _ => continue,
+            }
+            .to_srgba()
+            .to_u8_array();
+            // leave a pixel of gap around each cell, like the arena
+            for y in row * ICON_CELL + 1..(row + 1) * ICON_CELL - 1 {
+                for x in col * ICON_CELL + 1..(col + 1) * ICON_CELL - 1 {
+                    let i = (y * size + x) * 4;
+                    rgba[i..i + 4].copy_from_slice(&color);
+                }
+            }
+        }
+    }
+    Icon::from_rgba(rgba, size as u32, size as u32)
+        .inspect_err(|err| warn!("Failed to build the window icon: {err}"))
+        .ok()
+}
+
+// The winit window only exists a little after startup, so keep trying until it does.
+fn set_window_icon(
+    mut done: Local<bool>,
+    pickups: Res<Pickups>,
+    windows: NonSend<WinitWindows>,
+    primary: Query<Entity, With<PrimaryWindow>>,
+) {
+    if *done {
+        return;
+    }
+    let Some(window) = primary
+        .get_single()
+        .ok()
+        .and_then(|ent| windows.get_window(ent))
+    else {
+        return;
+    };
+    *done = true;
+    window.set_window_icon(icon(pickups.food.color()));
+}
+
+// A pointer while a menu is open, the usual arrow otherwise.
+fn update_cursor(
+    mut commands: Commands,
+    menus: Query<(), With<Menu>>,
+    primary: Query<(Entity, Option<&CursorIcon>), With<PrimaryWindow>>,
+) {
+    let Ok((window, current)) = primary.get_single() else {
+        return;
+    };
+    let wanted = CursorIcon::System(if menus.is_empty() {
+        SystemCursorIcon::Default
+    } else {
+        SystemCursorIcon::Pointer
+    });
+    if current != Some(&wanted) {
+        commands.entity(window).insert(wanted);
+    }
+}
@@ -0,0 +1,306 @@
+use bevy::prelude::*;
+use rand::Rng;
+use serde::Deserialize;
+use std::{collections::HashSet, time::Duration};
+
+use crate::{
+    free_cells, hot_reload::data_file, locale::Locale, progress::Progress, shop::ArenaTheme,
+    show_toast, spawn_food, GameMode, GameOverEvent, GameRng, Occupied, Position, Size, SnakeHead,
+    SnakeSegment, Trail, ARENA_HEIGHT, ARENA_WIDTH,
+};
+
+const WORLD_EVENT_INTERVAL_SECS: u64 = 20;
+const WORLD_EVENT_CHANCE: f32 = 0.5;
+const FRENZY_PELLETS: usize = 15;
+const BLACKOUT_MIN_RADIUS: f32 = 1.5;
+// how far the glow of a body segment reaches through a blackout
+const SEGMENT_LIGHT_RADIUS: f32 = 1.5;
+const GLOW_ALPHA: f32 = 0.25;
+
+#[derive(Clone, Copy, PartialEq, Eq, Deserialize)]
+enum WorldEvent {
+    FoodFrenzy,
+    Earthquake,
+    Blackout,
+}
+
+#[derive(Deserialize)]
+struct WorldEventDef {
+    event: WorldEvent,
+    // locale key of the toast shown when it starts
+    announcement: String,
+    duration_secs: f32,
+    modes: Vec<GameMode>,
+}
+
+// Every arena-wide event and the modes it can trigger in, read from
+// assets/world_events.ron.
+#[derive(Resource)]
+pub struct WorldEvents(Vec<WorldEventDef>);
+
+impl WorldEvents {
+    fn load() -> Self {
+        Self::parse(&data_file!("world_events.ron"))
+            .unwrap_or_else(|err| panic!("Broken world events: {err}"))
+    }
+
+    pub fn parse(contents: &str) -> ron::error::SpannedResult<Self> {
+        ron::from_str(contents).map(Self)
+    }
+}
+
+#[derive(Resource)]
+struct WorldEventTimer(Timer);
+
+#[derive(Resource, Default)]
+struct ActiveWorldEvent(Option<(WorldEvent, Timer)>);
+
+#[derive(Event)]
+struct WorldEventStarted(WorldEvent);
+
+#[derive(Event)]
+struct WorldEventEnded(WorldEvent);
+
+// Pellets spawned by a food frenzy, cleared again when it ends.
+#[derive(Component)]
+struct FrenzyFood;
+
+// Soft light drawn under a snake segment while the arena is dark.
+#[derive(Component)]
+struct Glow(Entity);
+
+pub struct WorldEventsPlugin;
+
+impl Plugin for WorldEventsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(WorldEventTimer(Timer::new(
+            Duration::from_secs(WORLD_EVENT_INTERVAL_SECS),
+            TimerMode::Repeating,
+        )))
+        .insert_resource(WorldEvents::load())
+        .insert_resource(ActiveWorldEvent::default())
+        .add_systems(
+            Update,
+            (
+                reset_world_events,
+                end_world_event,
+                world_event_scheduler,
+                (
+                    announce_world_event,
+                    food_frenzy,
+                    earthquake,
+                    snake_glow,
+                    blackout_fog,
+                ),
+            )
+                .chain(),
+        )
+        .add_event::<WorldEventStarted>()
+        .add_event::<WorldEventEnded>();
+    }
+}
+
+fn reset_world_events(
+    mut game_over_reader: EventReader<GameOverEvent>,
+    mut timer: ResMut<WorldEventTimer>,
+    mut active: ResMut<ActiveWorldEvent>,
+    mut ended_writer: EventWriter<WorldEventEnded>,
+) {
+    if game_over_reader.read().next().is_some() {
+        if let Some((event, _)) = active.0.take() {
+            ended_writer.send(WorldEventEnded(event));
+        }
+        timer.0.reset();
+    }
+}
+
+fn end_world_event(
+    time: Res<Time>,
+    mut active: ResMut<ActiveWorldEvent>,
+    mut ended_writer: EventWriter<WorldEventEnded>,
+) {
+    if let Some((event, event_timer)) = active.0.as_mut() {
+        if event_timer.tick(time.delta()).finished() {
+            ended_writer.send(WorldEventEnded(*event));
+            active.0 = None;
+        }
+    }
+}
+
+fn world_event_scheduler(
+    time: Res<Time>,
+    mode: Res<GameMode>,
+    events: Res<WorldEvents>,
+    mut timer: ResMut<WorldEventTimer>,
+    mut rng: ResMut<GameRng>,
+    mut active: ResMut<ActiveWorldEvent>,
+    mut started_writer: EventWriter<WorldEventStarted>,
+) {
+    if active.0.is_some() {
+        return;
+    }
+
+    if !timer.0.tick(time.delta()).just_finished() || rng.0.gen::<f32>() > WORLD_EVENT_CHANCE {
+        return;
+    }
+
+    let enabled: Vec<&WorldEventDef> = events
+        .0
+        .iter()
+        .filter(|def| def.modes.contains(&*mode))
+        .collect();
+    if enabled.is_empty() {
+        return;
+    }
+
+    let def = enabled[rng.0.gen_range(0..enabled.len())];
+    active.0 = Some((
+        def.event,
+        Timer::from_seconds(def.duration_secs, TimerMode::Once),
+    ));
+    started_writer.send(WorldEventStarted(def.event));
+}
+
+fn announce_world_event(
+    mut commands: Commands,
+    locale: Res<Locale>,
+    events: Res<WorldEvents>,
+    mut started_reader: EventReader<WorldEventStarted>,
+) {
+    for WorldEventStarted(event) in started_reader.read() {
+        if let Some(def) = events.0.iter().find(|def| def.event == *event) {
+            show_toast(&mut commands, locale.text(&def.announcement));
+        }
+    }
+}
+
+fn food_frenzy(
+    mut commands: Commands,
+    mut rng: ResMut<GameRng>,
+    mut started_reader: EventReader<WorldEventStarted>,
+    mut ended_reader: EventReader<WorldEventEnded>,
+    frenzy_food: Query<Entity, With<FrenzyFood>>,
+    occupied: Query<&Position, Occupied>,
+) {
+    for WorldEventStarted(event) in started_reader.read() {
+        if *event != WorldEvent::FoodFrenzy {
+            continue;
+        }
+        // only on free cells, never stacked on food or a snake
+        let mut free = free_cells(&occupied);
+        for _ in 0..FRENZY_PELLETS.min(free.len()) {
+            let position = free.swap_remove(rng.0.gen_range(0..free.len()));
+            let food = spawn_food(&mut commands, position);
+            commands.entity(food).insert(FrenzyFood);
+        }
+    }
+
+    for WorldEventEnded(event) in ended_reader.read() {
+        if *event == WorldEvent::FoodFrenzy {
+            for ent in frenzy_food.iter() {
+                commands.entity(ent).despawn();
+            }
+        }
+    }
+}
+
+fn earthquake(
+    mut rng: ResMut<GameRng>,
+    mut started_reader: EventReader<WorldEventStarted>,
+    segments: Query<&Position, (With<SnakeSegment>, Without<Trail>)>,
+    mut trails: Query<&mut Position, With<Trail>>,
+) {
+    if !started_reader
+        .read()
+        .any(|WorldEventStarted(event)| *event == WorldEvent::Earthquake)
+    {
+        return;
+    }
+
+    // walls move to random cells, never on top of a snake
+    let occupied: Vec<Position> = segments.iter().copied().collect();
+    for mut pos in trails.iter_mut() {
+        let new_pos = Position::random(&mut rng);
+        if !occupied.contains(&new_pos) {
+            *pos = new_pos;
+        }
+    }
+}
+
+// Snakes glow during a blackout and in the midnight arena.
+fn snake_glow(
+    mut commands: Commands,
+    active: Res<ActiveWorldEvent>,
+    progress: Res<Progress>,
+    segments: Query<(Entity, &Position, &Sprite), With<SnakeSegment>>,
+    mut glows: Query<(Entity, &Glow, &mut Position, &mut Sprite), Without<SnakeSegment>>,
+) {
+    let dark = matches!(active.0, Some((WorldEvent::Blackout, _)))
+        || progress.theme == ArenaTheme::Midnight;
+
+    let mut lit = HashSet::new();
+    for (ent, glow, mut pos, mut sprite) in glows.iter_mut() {
+        match segments.get(glow.0) {
+            Ok((segment, segment_pos, segment_sprite)) if dark => {
+                *pos = *segment_pos;
+                sprite.color = segment_sprite.color.with_alpha(GLOW_ALPHA);
+                lit.insert(segment);
+            }
+            _ => commands.entity(ent).despawn(),
+        }
+    }
+    if !dark {
+        return;
+    }
+
+    for (segment, pos, sprite) in segments.iter() {
+        if lit.contains(&segment) {
+            continue;
+        }
+        commands.spawn((
+            Sprite {
+                color: sprite.color.with_alpha(GLOW_ALPHA),
+                ..default()
+            },
+            // drawn under everything on the grid
+            Transform::from_xyz(0.0, 0.0, -1.0),
+            Glow(segment),
+            *pos,
+            Size::square(2.0),
+        ));
+    }
+}
+
+fn blackout_fog(
+    active: Res<ActiveWorldEvent>,
+    heads: Query<&Position, With<SnakeHead>>,
+    segments: Query<&Position, (With<SnakeSegment>, Without<SnakeHead>)>,
+    mut q: Query<(&Position, &mut Visibility), Without<SnakeHead>>,
+) {
+    // the visible radius around each head closes in over the blackout
+    let radius = match &active.0 {
+        Some((WorldEvent::Blackout, timer)) => {
+            let full = ARENA_WIDTH.max(ARENA_HEIGHT) as f32;
+            full - (full - BLACKOUT_MIN_RADIUS) * timer.fraction()
+        }
+        _ => f32::INFINITY,
+    };
+
+    let distance = |a: &Position, b: &Position| {
+        let dx = (a.x - b.x) as f32;
+        let dy = (a.y - b.y) as f32;
+        (dx * dx + dy * dy).sqrt()
+    };
+    for (pos, mut visibility) in q.iter_mut() {
+        // heads see the shrinking radius, the rest of the body lights its surroundings
+        let visible = heads.iter().any(|head| distance(head, pos) <= radius)
+            || segments
+                .iter()
+                .any(|segment| distance(segment, pos) <= SEGMENT_LIGHT_RADIUS);
+        visibility.set_if_neq(if visible {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        });
+    }
+}